@@ -1,4 +1,16 @@
-use std::fs;
+mod planner;
+mod report;
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+};
+
+use planner::{Goal, Mode};
+use report::InventoryReport;
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
@@ -29,6 +41,56 @@ impl Elf {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct RankedElf {
+    index: usize,
+    total: u64,
+}
+
+fn top_k_elves<R: BufRead>(reader: R, k: usize) -> io::Result<Vec<RankedElf>> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut push = |index: usize, total: u64| {
+        heap.push(Reverse((total, Reverse(index))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    };
+
+    let mut index = 0;
+    let mut total = None;
+    for line in reader.lines() {
+        let line = line?;
+        let item = line.trim();
+        if item.is_empty() {
+            if let Some(total) = total.take() {
+                push(index, total);
+                index += 1;
+            }
+            continue;
+        }
+        let calories: u32 = item
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        total = Some(
+            total
+                .unwrap_or(0u64)
+                .checked_add(u64::from(calories))
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "calorie total overflows u64")
+                })?,
+        );
+    }
+    if let Some(total) = total {
+        push(index, total);
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(index)))| RankedElf { index, total })
+        .collect())
+}
+
 pub fn solve_part_1(file_path: &str) -> Option<u32> {
     let data = load_file(file_path);
     let elves = parse_data(data);
@@ -38,15 +100,13 @@ pub fn solve_part_1(file_path: &str) -> Option<u32> {
         .max()
 }
 
-pub fn solve_part_2(file_path: &str) -> u32 {
-    let data = load_file(file_path);
-    let elves = parse_data(data);
-    let mut elves_inventory = elves
+pub fn solve_part_2(file_path: &str) -> u64 {
+    let file = File::open(file_path).expect("Should have been able to read the file");
+    top_k_elves(BufReader::new(file), 3)
+        .expect("Should have been able to parse the file")
         .into_iter()
-        .map(|elf| elf.inventory.into_iter().sum::<u32>())
-        .collect::<Vec<_>>();
-    elves_inventory.sort();
-    elves_inventory.into_iter().rev().take(3).sum()
+        .try_fold(0u64, |sum, elf| sum.checked_add(elf.total))
+        .expect("Top three calorie totals should fit in u64")
 }
 
 fn part_1(file_path: &str) {
@@ -59,10 +119,53 @@ fn part_2(file_path: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn report(file_path: &str, csv: bool) {
+    let elves = parse_data(load_file(file_path));
+    let report = InventoryReport::new(&elves);
+    if csv {
+        print!("{}", report.to_csv());
+    } else {
+        print!("{}", report);
+    }
+}
+
+fn balance(file_path: &str, goal: Goal, mode: Mode) {
+    let elves = parse_data(load_file(file_path));
+    match planner::plan(&elves, goal, mode) {
+        Ok(plan) => {
+            for transfer in &plan.transfers {
+                println!(
+                    "move item {} ({} calories) from elf {} to elf {}",
+                    transfer.item, transfer.calories, transfer.from, transfer.to
+                );
+            }
+            println!("Spread after balancing: {}", plan.spread());
+        }
+        Err(error) => println!("Balancing failed: {:?}", error),
+    }
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    match env::args().nth(1).as_deref() {
+        Some("report") => report(FILE_PATH, false),
+        Some("report-csv") => report(FILE_PATH, true),
+        Some("balance") => {
+            let mut goal = Goal::MinimiseSpread;
+            let mut mode = Mode::Heuristic;
+            for argument in env::args().skip(2) {
+                match argument.as_str() {
+                    "--exact" => mode = Mode::Exact,
+                    cap => goal = Goal::Cap(cap.parse().expect("Cap should be a number")),
+                }
+            }
+            balance(FILE_PATH, goal, mode);
+        }
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -96,4 +199,62 @@ mod tests {
         let result = solve_part_2("./resources/test_data.txt");
         assert_eq!(result, 45000);
     }
+
+    #[test]
+    fn top_k_elves_returns_ranked_elves_with_indices() {
+        let file = File::open("./resources/test_data.txt").unwrap();
+        let result = top_k_elves(BufReader::new(file), 3).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                RankedElf {
+                    index: 3,
+                    total: 24000
+                },
+                RankedElf {
+                    index: 2,
+                    total: 11000
+                },
+                RankedElf {
+                    index: 4,
+                    total: 10000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn top_k_elves_handles_ties_and_small_inputs() {
+        let data = "100\n\n\n50\n50\n\n7\n";
+        let result = top_k_elves(data.as_bytes(), 5).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                RankedElf {
+                    index: 0,
+                    total: 100
+                },
+                RankedElf {
+                    index: 1,
+                    total: 100
+                },
+                RankedElf { index: 2, total: 7 },
+            ]
+        );
+        assert_eq!(top_k_elves(data.as_bytes(), 0).unwrap(), vec![]);
+        assert!(top_k_elves("12\nab\n".as_bytes(), 1).is_err());
+    }
+
+    #[test]
+    fn top_k_elves_sums_beyond_u32() {
+        let data = format!("{}\n{}\n{}\n\n1\n", u32::MAX, u32::MAX, u32::MAX);
+        let result = top_k_elves(data.as_bytes(), 1).unwrap();
+        assert_eq!(
+            result,
+            vec![RankedElf {
+                index: 0,
+                total: 3 * u64::from(u32::MAX)
+            }]
+        );
+    }
 }
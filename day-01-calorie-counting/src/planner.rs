@@ -0,0 +1,380 @@
+use std::cmp::Reverse;
+
+use crate::Elf;
+
+const MAX_EXACT_ASSIGNMENTS: u64 = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    MinimiseSpread,
+    Cap(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Heuristic,
+    Exact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanError {
+    Infeasible,
+    Unsolved,
+    TooLarge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: usize,
+    pub to: usize,
+    pub item: usize,
+    pub calories: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub transfers: Vec<Transfer>,
+    pub totals: Vec<u64>,
+}
+
+impl Plan {
+    pub fn spread(&self) -> u64 {
+        spread(&self.totals)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    owner: usize,
+    index: usize,
+    calories: u32,
+}
+
+fn spread(totals: &[u64]) -> u64 {
+    match (totals.iter().max(), totals.iter().min()) {
+        (Some(max), Some(min)) => max - min,
+        _ => 0,
+    }
+}
+
+fn items(elves: &[Elf]) -> Vec<Item> {
+    elves
+        .iter()
+        .enumerate()
+        .flat_map(|(owner, elf)| {
+            elf.inventory
+                .iter()
+                .enumerate()
+                .map(move |(index, &calories)| Item {
+                    owner,
+                    index,
+                    calories,
+                })
+        })
+        .collect()
+}
+
+fn build_plan(items: &[Item], assignment: &[usize], elves_count: usize) -> Plan {
+    let mut totals = vec![0; elves_count];
+    let mut transfers = Vec::new();
+    for (item, &to) in items.iter().zip(assignment) {
+        totals[to] += u64::from(item.calories);
+        if item.owner != to {
+            transfers.push(Transfer {
+                from: item.owner,
+                to,
+                item: item.index,
+                calories: item.calories,
+            });
+        }
+    }
+    Plan { transfers, totals }
+}
+
+pub fn plan(elves: &[Elf], goal: Goal, mode: Mode) -> Result<Plan, PlanError> {
+    let total: u64 = elves
+        .iter()
+        .flat_map(|elf| elf.inventory.iter())
+        .copied()
+        .map(u64::from)
+        .sum();
+    if let Goal::Cap(cap) = goal {
+        let largest_item = elves.iter().flat_map(|elf| elf.inventory.iter()).max();
+        if cap
+            .checked_mul(elves.len() as u64)
+            .is_some_and(|limit| total > limit)
+            || largest_item.is_some_and(|&item| u64::from(item) > cap)
+        {
+            return Err(PlanError::Infeasible);
+        }
+    }
+    match mode {
+        Mode::Heuristic => plan_heuristic(elves, goal),
+        Mode::Exact => plan_exact(elves, goal),
+    }
+}
+
+fn best_move(
+    items: &[Item],
+    assignment: &[usize],
+    totals: &[u64],
+    giver: usize,
+    receiver: usize,
+) -> Option<(usize, Option<usize>, u64)> {
+    let difference = totals[giver].checked_sub(totals[receiver])?;
+    let carried_by = |elf: usize| (0..items.len()).filter(move |&item| assignment[item] == elf);
+    let mut candidates = Vec::new();
+    for given in carried_by(giver) {
+        candidates.push((given, None, u64::from(items[given].calories)));
+        for taken in carried_by(receiver) {
+            if items[given].calories > items[taken].calories {
+                let moved = items[given].calories - items[taken].calories;
+                candidates.push((given, Some(taken), u64::from(moved)));
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|&(_, _, moved)| moved > 0 && moved < difference)
+        .min_by_key(|&(_, _, moved)| (difference as i128 - 2 * moved as i128).abs())
+}
+
+fn plan_heuristic(elves: &[Elf], goal: Goal) -> Result<Plan, PlanError> {
+    let items = items(elves);
+    let mut assignment = items.iter().map(|item| item.owner).collect::<Vec<_>>();
+    let mut totals = elves
+        .iter()
+        .map(|elf| elf.inventory.iter().copied().map(u64::from).sum::<u64>())
+        .collect::<Vec<_>>();
+
+    if elves.is_empty() {
+        return Ok(build_plan(&items, &assignment, 0));
+    }
+
+    loop {
+        let mut order = (0..totals.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&elf| totals[elf]);
+        let heaviest = order[order.len() - 1];
+        let lightest = order[0];
+        if let Goal::Cap(cap) = goal {
+            if totals[heaviest] <= cap {
+                break;
+            }
+        }
+
+        let pairs = order
+            .iter()
+            .filter(|&&elf| elf != heaviest)
+            .map(|&receiver| (heaviest, receiver))
+            .chain(
+                order
+                    .iter()
+                    .rev()
+                    .filter(|&&elf| elf != heaviest && elf != lightest)
+                    .map(|&giver| (giver, lightest)),
+            );
+        let best_move = pairs
+            .filter_map(|(giver, receiver)| {
+                best_move(&items, &assignment, &totals, giver, receiver)
+                    .map(|(given, taken, moved)| (giver, receiver, given, taken, moved))
+            })
+            .next();
+
+        let Some((giver, receiver, given, taken, moved)) = best_move else {
+            break;
+        };
+        assignment[given] = receiver;
+        if let Some(taken) = taken {
+            assignment[taken] = giver;
+        }
+        totals[giver] -= moved;
+        totals[receiver] += moved;
+    }
+
+    let plan = build_plan(&items, &assignment, elves.len());
+    match goal {
+        Goal::Cap(cap) if plan.totals.iter().any(|&total| total > cap) => Err(PlanError::Unsolved),
+        _ => Ok(plan),
+    }
+}
+
+struct ExactSearch<'a> {
+    items: &'a [Item],
+    goal: Goal,
+    remaining: Vec<u64>,
+    average_floor: u64,
+    average_ceil: u64,
+    totals: Vec<u64>,
+    assignment: Vec<usize>,
+    best: Option<(u64, usize, Vec<usize>)>,
+}
+
+impl ExactSearch<'_> {
+    fn lower_bound(&self, depth: usize) -> u64 {
+        match self.goal {
+            Goal::MinimiseSpread => {
+                let max = *self.totals.iter().max().unwrap();
+                let min = *self.totals.iter().min().unwrap();
+                let max = max.max(self.average_ceil);
+                let min = (min + self.remaining[depth]).min(self.average_floor);
+                max.saturating_sub(min)
+            }
+            Goal::Cap(_) => 0,
+        }
+    }
+
+    fn search(&mut self, depth: usize, transfers: usize) {
+        let bound = self.lower_bound(depth);
+        if let Some((best_spread, best_transfers, _)) = &self.best {
+            if (bound, transfers) >= (*best_spread, *best_transfers) {
+                return;
+            }
+        }
+        if depth == self.items.len() {
+            let spread = match self.goal {
+                Goal::MinimiseSpread => spread(&self.totals),
+                Goal::Cap(_) => 0,
+            };
+            self.best = Some((spread, transfers, self.assignment.clone()));
+            return;
+        }
+
+        let item = self.items[depth];
+        let owner_first = std::iter::once(item.owner)
+            .chain((0..self.totals.len()).filter(|&elf| elf != item.owner));
+        let calories = u64::from(item.calories);
+        for elf in owner_first {
+            if let Goal::Cap(cap) = self.goal {
+                if self.totals[elf] + calories > cap {
+                    continue;
+                }
+            }
+            self.totals[elf] += calories;
+            self.assignment[depth] = elf;
+            self.search(depth + 1, transfers + usize::from(elf != item.owner));
+            self.totals[elf] -= calories;
+        }
+    }
+}
+
+fn plan_exact(elves: &[Elf], goal: Goal) -> Result<Plan, PlanError> {
+    let mut items = items(elves);
+    let assignments = (elves.len() as u64).checked_pow(items.len() as u32);
+    if assignments.is_none_or(|assignments| assignments > MAX_EXACT_ASSIGNMENTS) {
+        return Err(PlanError::TooLarge);
+    }
+    if elves.is_empty() {
+        return Ok(build_plan(&items, &[], 0));
+    }
+    items.sort_by_key(|item| Reverse(item.calories));
+
+    let total: u64 = items.iter().map(|item| u64::from(item.calories)).sum();
+    let mut remaining = vec![0; items.len() + 1];
+    for depth in (0..items.len()).rev() {
+        remaining[depth] = remaining[depth + 1] + u64::from(items[depth].calories);
+    }
+    let elves_count = elves.len() as u64;
+    let mut search = ExactSearch {
+        items: &items,
+        goal,
+        remaining,
+        average_floor: total / elves_count,
+        average_ceil: total.div_ceil(elves_count),
+        totals: vec![0; elves.len()],
+        assignment: vec![0; items.len()],
+        best: None,
+    };
+    search.search(0, 0);
+
+    let (_, _, assignment) = search.best.ok_or(PlanError::Infeasible)?;
+    let mut plan = build_plan(&items, &assignment, elves.len());
+    plan.transfers
+        .sort_by_key(|transfer| (transfer.from, transfer.item));
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    fn apply(elves: &[Elf], plan: &Plan) -> Vec<u64> {
+        let mut totals = elves
+            .iter()
+            .map(|elf| elf.inventory.iter().copied().map(u64::from).sum::<u64>())
+            .collect::<Vec<_>>();
+        for transfer in &plan.transfers {
+            assert_eq!(
+                elves[transfer.from].inventory[transfer.item],
+                transfer.calories
+            );
+            totals[transfer.from] -= u64::from(transfer.calories);
+            totals[transfer.to] += u64::from(transfer.calories);
+        }
+        totals
+    }
+
+    #[test]
+    fn heuristic_reduces_spread() {
+        let elves = parse_data(load_file("./resources/test_data.txt"));
+        let plan = plan(&elves, Goal::MinimiseSpread, Mode::Heuristic).unwrap();
+        assert_eq!(apply(&elves, &plan), plan.totals);
+        assert!(plan.spread() < 20000);
+    }
+
+    #[test]
+    fn exact_finds_minimal_spread_with_fewest_transfers() {
+        let elves = vec![
+            Elf::new(vec![5, 4, 3]),
+            Elf::new(vec![2]),
+            Elf::new(vec![1, 3]),
+        ];
+        let plan = plan(&elves, Goal::MinimiseSpread, Mode::Exact).unwrap();
+        assert_eq!(plan.spread(), 0);
+        assert_eq!(plan.totals, vec![6, 6, 6]);
+        assert_eq!(plan.transfers.len(), 3);
+        assert_eq!(apply(&elves, &plan), plan.totals);
+    }
+
+    #[test]
+    fn cap_moves_items_until_every_elf_is_under_it() {
+        let elves = parse_data(load_file("./resources/test_data.txt"));
+        for mode in [Mode::Heuristic, Mode::Exact] {
+            let plan = plan(&elves, Goal::Cap(12000), mode).unwrap();
+            assert!(plan.totals.iter().all(|&total| total <= 12000));
+            assert_eq!(apply(&elves, &plan), plan.totals);
+        }
+        let exact = plan(&elves, Goal::Cap(12000), Mode::Exact).unwrap();
+        assert_eq!(exact.transfers.len(), 3);
+        assert_eq!(
+            plan(&elves, Goal::Cap(10000), Mode::Exact),
+            Err(PlanError::Infeasible)
+        );
+    }
+
+    #[test]
+    fn totals_beyond_u32_are_planned() {
+        let elves = vec![
+            Elf::new(vec![u32::MAX, u32::MAX, u32::MAX]),
+            Elf::new(vec![u32::MAX]),
+        ];
+        for mode in [Mode::Heuristic, Mode::Exact] {
+            let plan = plan(&elves, Goal::MinimiseSpread, mode).unwrap();
+            assert_eq!(plan.totals, vec![2 * u64::from(u32::MAX); 2]);
+            assert_eq!(apply(&elves, &plan), plan.totals);
+        }
+        assert_eq!(
+            plan(&elves, Goal::Cap(u64::from(u32::MAX)), Mode::Heuristic),
+            Err(PlanError::Infeasible)
+        );
+    }
+
+    #[test]
+    fn exact_mode_rejects_large_inputs() {
+        let elves = parse_data(load_file("./resources/puzzle.txt"));
+        assert_eq!(
+            plan(&elves, Goal::MinimiseSpread, Mode::Exact),
+            Err(PlanError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+
+use crate::Elf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElfStats {
+    pub index: usize,
+    pub item_count: usize,
+    pub min_item: Option<u32>,
+    pub max_item: Option<u32>,
+    pub mean_item: Option<f64>,
+    pub median_item: Option<f64>,
+    pub total: u64,
+    pub percentile: f64,
+    pub outlier: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    pub lower_quartile: f64,
+    pub upper_quartile: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryReport {
+    pub elves: Vec<ElfStats>,
+    pub distribution: Option<Distribution>,
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+impl Distribution {
+    fn new(totals: &[u64]) -> Option<Self> {
+        if totals.is_empty() {
+            return None;
+        }
+        let mut sorted = totals.iter().map(|&total| total as f64).collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+        let mean = mean(&sorted);
+        let variance = sorted
+            .iter()
+            .map(|total| (total - mean).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+        Some(Self {
+            min: *totals.iter().min().unwrap(),
+            max: *totals.iter().max().unwrap(),
+            mean,
+            median: quantile(&sorted, 0.5),
+            std_dev: variance.sqrt(),
+            lower_quartile: quantile(&sorted, 0.25),
+            upper_quartile: quantile(&sorted, 0.75),
+        })
+    }
+
+    fn is_outlier(&self, total: u64) -> bool {
+        let fence = 1.5 * (self.upper_quartile - self.lower_quartile);
+        let total = total as f64;
+        total < self.lower_quartile - fence || total > self.upper_quartile + fence
+    }
+}
+
+impl InventoryReport {
+    pub fn new(elves: &[Elf]) -> Self {
+        let totals = elves
+            .iter()
+            .map(|elf| elf.inventory.iter().copied().map(u64::from).sum::<u64>())
+            .collect::<Vec<_>>();
+        let distribution = Distribution::new(&totals);
+        let mut sorted_totals = totals.clone();
+        sorted_totals.sort_unstable();
+
+        let elves = elves
+            .iter()
+            .zip(&totals)
+            .enumerate()
+            .map(|(index, (elf, &total))| {
+                let mut items = elf
+                    .inventory
+                    .iter()
+                    .map(|&item| item as f64)
+                    .collect::<Vec<_>>();
+                items.sort_by(f64::total_cmp);
+                let (mean_item, median_item) = if items.is_empty() {
+                    (None, None)
+                } else {
+                    (Some(mean(&items)), Some(quantile(&items, 0.5)))
+                };
+                let at_most = sorted_totals.partition_point(|&other| other <= total);
+                ElfStats {
+                    index,
+                    item_count: elf.inventory.len(),
+                    min_item: elf.inventory.iter().min().copied(),
+                    max_item: elf.inventory.iter().max().copied(),
+                    mean_item,
+                    median_item,
+                    total,
+                    percentile: 100.0 * at_most as f64 / totals.len() as f64,
+                    outlier: distribution
+                        .as_ref()
+                        .is_some_and(|distribution| distribution.is_outlier(total)),
+                }
+            })
+            .collect();
+
+        Self {
+            elves,
+            distribution,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let mut csv = String::from(
+            "elf,items,min_item,max_item,mean_item,median_item,total,percentile,outlier\n",
+        );
+        for elf in &self.elves {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.2},{}\n",
+                elf.index,
+                elf.item_count,
+                optional(elf.min_item.map(|item| item.to_string())),
+                optional(elf.max_item.map(|item| item.to_string())),
+                optional(elf.mean_item.map(|item| format!("{:.2}", item))),
+                optional(elf.median_item.map(|item| format!("{:.2}", item))),
+                elf.total,
+                elf.percentile,
+                elf.outlier
+            ));
+        }
+        csv
+    }
+}
+
+impl fmt::Display for InventoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        writeln!(
+            f,
+            "{:>5} {:>5} {:>8} {:>8} {:>10} {:>10} {:>10} {:>10} {:>7}",
+            "elf", "items", "min", "max", "mean", "median", "total", "percentile", "outlier"
+        )?;
+        for elf in &self.elves {
+            writeln!(
+                f,
+                "{:>5} {:>5} {:>8} {:>8} {:>10} {:>10} {:>10} {:>10.2} {:>7}",
+                elf.index,
+                elf.item_count,
+                optional(elf.min_item.map(|item| item.to_string())),
+                optional(elf.max_item.map(|item| item.to_string())),
+                optional(elf.mean_item.map(|item| format!("{:.2}", item))),
+                optional(elf.median_item.map(|item| format!("{:.2}", item))),
+                elf.total,
+                elf.percentile,
+                if elf.outlier { "yes" } else { "" }
+            )?;
+        }
+        if let Some(distribution) = &self.distribution {
+            writeln!(
+                f,
+                "totals: min {} max {} mean {:.2} median {:.2} std dev {:.2} q1 {:.2} q3 {:.2}",
+                distribution.min,
+                distribution.max,
+                distribution.mean,
+                distribution.median,
+                distribution.std_dev,
+                distribution.lower_quartile,
+                distribution.upper_quartile
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    #[test]
+    fn report_contains_per_elf_statistics() {
+        let elves = parse_data(load_file("./resources/test_data.txt"));
+        let report = InventoryReport::new(&elves);
+        assert_eq!(
+            report.elves[0],
+            ElfStats {
+                index: 0,
+                item_count: 3,
+                min_item: Some(1000),
+                max_item: Some(3000),
+                mean_item: Some(2000.0),
+                median_item: Some(2000.0),
+                total: 6000,
+                percentile: 40.0,
+                outlier: false,
+            }
+        );
+        assert_eq!(report.elves[3].percentile, 100.0);
+        let distribution = report.distribution.unwrap();
+        assert_eq!(distribution.min, 4000);
+        assert_eq!(distribution.max, 24000);
+        assert_eq!(distribution.median, 10000.0);
+        assert_eq!(distribution.lower_quartile, 6000.0);
+        assert_eq!(distribution.upper_quartile, 11000.0);
+    }
+
+    #[test]
+    fn report_detects_outliers() {
+        let elves = [10, 11, 12, 13, 14, 100]
+            .into_iter()
+            .map(|calories| Elf::new(vec![calories]))
+            .collect::<Vec<_>>();
+        let report = InventoryReport::new(&elves);
+        let outliers = report
+            .elves
+            .iter()
+            .filter(|elf| elf.outlier)
+            .map(|elf| elf.index)
+            .collect::<Vec<_>>();
+        assert_eq!(outliers, vec![5]);
+    }
+
+    #[test]
+    fn report_sums_beyond_u32() {
+        let elves = vec![Elf::new(vec![u32::MAX, u32::MAX]), Elf::new(vec![1])];
+        let report = InventoryReport::new(&elves);
+        assert_eq!(report.elves[0].total, 2 * u64::from(u32::MAX));
+        assert_eq!(report.elves[0].percentile, 100.0);
+        assert_eq!(report.distribution.unwrap().max, 2 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn report_renders_csv() {
+        let elves = vec![Elf::new(vec![1, 2]), Elf::new(vec![])];
+        let report = InventoryReport::new(&elves);
+        assert_eq!(
+            report.to_csv(),
+            "elf,items,min_item,max_item,mean_item,median_item,total,percentile,outlier\n\
+             0,2,1,2,1.50,1.50,3,100.00,false\n\
+             1,0,,,,,0,50.00,false\n"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::{collections::HashMap, fs};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    Parse { line: usize, message: String },
+    UnknownWeapon { line: usize, name: String },
+    UnknownSymbol { line: usize, symbol: String },
+    DuplicateWeapon(String),
+    DuplicateSymbol(String),
+    MissingOutcome(Outcome),
+    EvenWeaponCount(usize),
+    SelfBeat(String),
+    NotTournament(String, String),
+    Unbalanced(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Weapon {
+    pub name: String,
+    pub score: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    weapons: Vec<Weapon>,
+    beats: Vec<Vec<bool>>,
+    outcome_scores: HashMap<Outcome, usize>,
+    opponent_symbols: HashMap<String, usize>,
+    my_symbols: HashMap<String, usize>,
+    outcome_symbols: HashMap<String, Outcome>,
+}
+
+impl Game {
+    pub fn load(file_path: &str) -> Result<Self, GameError> {
+        let definition =
+            fs::read_to_string(file_path).expect("Should have been able to read the file");
+        Self::parse(&definition)
+    }
+
+    pub fn parse(definition: &str) -> Result<Self, GameError> {
+        let mut weapons = Vec::<Weapon>::new();
+        let mut relations = Vec::new();
+        let mut outcome_scores = HashMap::new();
+        let mut opponent_symbols = HashMap::new();
+        let mut my_symbols = HashMap::new();
+        let mut outcome_symbols = HashMap::new();
+
+        for (line_idx, line) in definition.lines().enumerate() {
+            let line_number = line_idx + 1;
+            let parse_error = |message: &str| GameError::Parse {
+                line: line_number,
+                message: message.to_string(),
+            };
+            let parse_score = |score: &str| {
+                score
+                    .parse::<usize>()
+                    .map_err(|_| parse_error("score should be a number"))
+            };
+            let fields = line.split_ascii_whitespace().collect::<Vec<_>>();
+            match fields.as_slice() {
+                [] => {}
+                ["weapon", name, score, opponent_symbol, my_symbol] => {
+                    if weapons.iter().any(|weapon| &weapon.name == name) {
+                        return Err(GameError::DuplicateWeapon(name.to_string()));
+                    }
+                    let index = weapons.len();
+                    weapons.push(Weapon {
+                        name: name.to_string(),
+                        score: parse_score(score)?,
+                    });
+                    insert_symbol(&mut opponent_symbols, opponent_symbol, index)?;
+                    insert_symbol(&mut my_symbols, my_symbol, index)?;
+                }
+                ["beats", winner, loser] => {
+                    relations.push((line_number, winner.to_string(), loser.to_string()))
+                }
+                ["outcome", outcome, score, symbol] => {
+                    let outcome = match *outcome {
+                        "lose" => Outcome::Lose,
+                        "draw" => Outcome::Draw,
+                        "win" => Outcome::Win,
+                        _ => return Err(parse_error("outcome should be lose, draw or win")),
+                    };
+                    outcome_scores.insert(outcome, parse_score(score)?);
+                    insert_symbol(&mut outcome_symbols, symbol, outcome)?;
+                }
+                _ => return Err(parse_error("unsupported definition")),
+            }
+        }
+
+        let weapon_index = |line: usize, name: &str| {
+            weapons
+                .iter()
+                .position(|weapon| weapon.name == name)
+                .ok_or_else(|| GameError::UnknownWeapon {
+                    line,
+                    name: name.to_string(),
+                })
+        };
+        let mut beats = vec![vec![false; weapons.len()]; weapons.len()];
+        for (line, winner, loser) in &relations {
+            beats[weapon_index(*line, winner)?][weapon_index(*line, loser)?] = true;
+        }
+
+        for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+            if !outcome_scores.contains_key(&outcome) {
+                return Err(GameError::MissingOutcome(outcome));
+            }
+        }
+
+        let game = Self {
+            weapons,
+            beats,
+            outcome_scores,
+            opponent_symbols,
+            my_symbols,
+            outcome_symbols,
+        };
+        game.validate()?;
+        Ok(game)
+    }
+
+    fn validate(&self) -> Result<(), GameError> {
+        let weapons_count = self.weapons.len();
+        if weapons_count.is_multiple_of(2) {
+            return Err(GameError::EvenWeaponCount(weapons_count));
+        }
+        for (i, weapon) in self.weapons.iter().enumerate() {
+            if self.beats[i][i] {
+                return Err(GameError::SelfBeat(weapon.name.clone()));
+            }
+            for j in (i + 1)..weapons_count {
+                if self.beats[i][j] == self.beats[j][i] {
+                    return Err(GameError::NotTournament(
+                        weapon.name.clone(),
+                        self.weapons[j].name.clone(),
+                    ));
+                }
+            }
+            let wins = self.beats[i].iter().filter(|&&beats| beats).count();
+            if wins != weapons_count / 2 {
+                return Err(GameError::Unbalanced(weapon.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn outcome(&self, my: usize, opponent: usize) -> Outcome {
+        if self.beats[my][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][my] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn play(&self, my: usize, opponent: usize) -> usize {
+        self.outcome_scores[&self.outcome(my, opponent)] + self.weapons[my].score
+    }
+
+    pub fn get_expected_hand(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.weapons.len())
+            .filter(|&my| self.outcome(my, opponent) == outcome)
+            .max_by_key(|&my| self.weapons[my].score)
+            .unwrap()
+    }
+
+    fn parse_rounds<T>(
+        &self,
+        data: &str,
+        second_symbols: &HashMap<String, T>,
+    ) -> Result<Vec<(usize, T)>, GameError>
+    where
+        T: Copy,
+    {
+        data.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_idx, line)| {
+                let mut splitted = line.split_ascii_whitespace();
+                let opponent =
+                    lookup_symbol(&self.opponent_symbols, splitted.next(), line_idx + 1)?;
+                let second = lookup_symbol(second_symbols, splitted.next(), line_idx + 1)?;
+                Ok((opponent, second))
+            })
+            .collect()
+    }
+
+    pub fn solve_part_1(&self, data: &str) -> Result<usize, GameError> {
+        let rounds = self.parse_rounds(data, &self.my_symbols)?;
+        Ok(rounds
+            .into_iter()
+            .map(|(opponent, my)| self.play(my, opponent))
+            .sum())
+    }
+
+    pub fn solve_part_2(&self, data: &str) -> Result<usize, GameError> {
+        let rounds = self.parse_rounds(data, &self.outcome_symbols)?;
+        Ok(rounds
+            .into_iter()
+            .map(|(opponent, outcome)| {
+                self.play(self.get_expected_hand(opponent, outcome), opponent)
+            })
+            .sum())
+    }
+}
+
+fn lookup_symbol<T>(
+    symbols: &HashMap<String, T>,
+    symbol: Option<&str>,
+    line: usize,
+) -> Result<T, GameError>
+where
+    T: Copy,
+{
+    let symbol = symbol.unwrap_or_default();
+    symbols
+        .get(symbol)
+        .copied()
+        .ok_or_else(|| GameError::UnknownSymbol {
+            line,
+            symbol: symbol.to_string(),
+        })
+}
+
+fn insert_symbol<T>(
+    symbols: &mut HashMap<String, T>,
+    symbol: &str,
+    value: T,
+) -> Result<(), GameError> {
+    if symbols.insert(symbol.to_string(), value).is_some() {
+        return Err(GameError::DuplicateSymbol(symbol.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_file;
+
+    #[test]
+    fn rock_paper_scissors_definition_matches_hand() {
+        let game = Game::load("./resources/rock_paper_scissors.game").unwrap();
+        let data = load_file("./resources/test_data.txt");
+        assert_eq!(game.solve_part_1(&data), Ok(15));
+        assert_eq!(game.solve_part_2(&data), Ok(12));
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock() {
+        let game = Game::load("./resources/rock_paper_scissors_lizard_spock.game").unwrap();
+        let data = load_file("./resources/test_data_lizard_spock.txt");
+        assert_eq!(game.solve_part_1(&data), Ok(32));
+        assert_eq!(game.solve_part_2(&data), Ok(30));
+    }
+
+    #[test]
+    fn definition_must_be_a_balanced_tournament() {
+        let weapons = "weapon Rock 1 A X\nweapon Paper 2 B Y\nweapon Scissors 3 C Z\n";
+        let outcomes = "outcome lose 0 X\noutcome draw 3 Y\noutcome win 6 Z\n";
+        let game = |relations: &str| Game::parse(&format!("{weapons}{relations}{outcomes}"));
+
+        assert_eq!(
+            game("beats Rock Scissors\nbeats Paper Rock\n"),
+            Err(GameError::NotTournament(
+                "Paper".to_string(),
+                "Scissors".to_string()
+            ))
+        );
+        assert_eq!(
+            game("beats Rock Scissors\nbeats Paper Rock\nbeats Paper Scissors\n"),
+            Err(GameError::Unbalanced("Paper".to_string()))
+        );
+        assert_eq!(
+            game("beats Rock Rock\n"),
+            Err(GameError::SelfBeat("Rock".to_string()))
+        );
+        assert_eq!(
+            game("beats Rock Lizard\n"),
+            Err(GameError::UnknownWeapon {
+                line: 4,
+                name: "Lizard".to_string()
+            })
+        );
+        assert_eq!(
+            Game::parse(&format!(
+                "weapon Rock 1 A X\nweapon Paper 2 B Y\n{outcomes}"
+            )),
+            Err(GameError::EvenWeaponCount(2))
+        );
+    }
+}
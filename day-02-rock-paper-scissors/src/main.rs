@@ -1,4 +1,12 @@
-use std::{cmp::Ordering, fs};
+mod game;
+mod strategy;
+mod tournament;
+
+use std::{cmp::Ordering, env, fs};
+
+use game::Game;
+use strategy::StrategyReport;
+use tournament::{Fixed, FrequencyCounter, Random, ReplayGuide, Strategy, WinStayLoseShift};
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
@@ -64,6 +72,8 @@ pub enum Hand {
 }
 
 impl Hand {
+    pub const ALL: [Hand; 3] = [Hand::Rock, Hand::Paper, Hand::Scissors];
+
     pub fn get_score(&self) -> usize {
         match self {
             Self::Rock => 1,
@@ -156,10 +166,64 @@ fn part_2(file_path: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn play_game(definition_path: &str, file_path: &str) {
+    let game = match Game::load(definition_path) {
+        Ok(game) => game,
+        Err(error) => {
+            println!("Invalid game definition: {:?}", error);
+            return;
+        }
+    };
+    let data = load_file(file_path);
+    println!("Part 1 result: {:?}", game.solve_part_1(&data));
+    println!("Part 2 result: {:?}", game.solve_part_2(&data));
+}
+
+fn strategy_report(file_path: &str) {
+    let data = load_file(file_path);
+    let rounds = part_1::parse_data(data);
+    print!("{}", StrategyReport::new(&rounds));
+}
+
+fn tournament(file_path: &str, rounds: usize) {
+    let guide = part_1::parse_data(load_file(file_path));
+    let mut bots: Vec<Box<dyn Strategy>> = vec![
+        Box::new(Fixed(Hand::Rock)),
+        Box::new(Fixed(Hand::Paper)),
+        Box::new(Fixed(Hand::Scissors)),
+        Box::new(Random::new(2022)),
+        Box::new(FrequencyCounter::default()),
+        Box::new(WinStayLoseShift::new(Hand::Rock)),
+    ];
+    if let Some(guide) = ReplayGuide::new(&guide) {
+        bots.push(Box::new(guide));
+    }
+    print!("{}", tournament::play_league(&mut bots, rounds));
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    let args = env::args().collect::<Vec<_>>();
+    match args.get(1).map(String::as_str) {
+        Some("game") => {
+            let definition_path = args
+                .get(2)
+                .map_or("./resources/rock_paper_scissors.game", String::as_str);
+            let file_path = args.get(3).map_or(FILE_PATH, String::as_str);
+            play_game(definition_path, file_path);
+        }
+        Some("strategy") => strategy_report(FILE_PATH),
+        Some("tournament") => {
+            let rounds = args.get(2).map_or(1000, |rounds| {
+                rounds.parse().expect("Rounds should be a number")
+            });
+            tournament(FILE_PATH, rounds);
+        }
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,263 @@
+use std::fmt;
+
+use crate::Hand;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrium {
+    pub my_strategy: [f64; 3],
+    pub opponent_strategy: [f64; 3],
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyReport {
+    pub rounds: usize,
+    pub opponent_frequencies: [usize; 3],
+    pub guide_score: usize,
+    pub frequency_response: Hand,
+    pub frequency_response_score: usize,
+    pub best_response_score: usize,
+    pub equilibrium: Equilibrium,
+    pub equilibrium_expected_score: f64,
+}
+
+fn hand_index(hand: &Hand) -> usize {
+    Hand::ALL.iter().position(|other| other == hand).unwrap()
+}
+
+fn scoring_matrix() -> Vec<Vec<f64>> {
+    Hand::ALL
+        .iter()
+        .map(|my| {
+            Hand::ALL
+                .iter()
+                .map(|opponent| my.play(opponent) as f64)
+                .collect()
+        })
+        .collect()
+}
+
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let size = matrix.len();
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|&a, &b| matrix[a][column].abs().total_cmp(&matrix[b][column].abs()))?;
+        if matrix[pivot][column].abs() < EPSILON {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        let pivot_row = matrix[column].clone();
+        for (row, values) in matrix.iter_mut().enumerate() {
+            if row != column {
+                let factor = values[column] / pivot_row[column];
+                for (value, pivot_value) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+    Some(
+        (0..size)
+            .map(|row| matrix[row][size] / matrix[row][row])
+            .collect(),
+    )
+}
+
+fn mix_on_support(
+    payoff: impl Fn(usize, usize) -> f64,
+    support: &[usize],
+    against: &[usize],
+) -> Option<(Vec<f64>, f64)> {
+    let k = support.len();
+    let mut system = against
+        .iter()
+        .map(|&other| {
+            let mut equation = support
+                .iter()
+                .map(|&own| payoff(own, other))
+                .collect::<Vec<_>>();
+            equation.push(-1.0);
+            equation.push(0.0);
+            equation
+        })
+        .collect::<Vec<_>>();
+    let mut normalisation = vec![1.0; k];
+    normalisation.push(0.0);
+    normalisation.push(1.0);
+    system.push(normalisation);
+
+    let solution = solve_linear_system(system)?;
+    let value = solution[k];
+    let mut mix = vec![0.0; Hand::ALL.len()];
+    for (&own, &probability) in support.iter().zip(&solution) {
+        if probability < -EPSILON {
+            return None;
+        }
+        mix[own] = probability.max(0.0);
+    }
+    Some((mix, value))
+}
+
+fn subsets(size: usize) -> impl Iterator<Item = Vec<usize>> {
+    (1..(1 << size)).map(move |mask: usize| (0..size).filter(|&i| mask & (1 << i) != 0).collect())
+}
+
+pub fn find_equilibrium() -> Equilibrium {
+    let matrix = scoring_matrix();
+    for my_support in subsets(Hand::ALL.len()) {
+        for opponent_support in subsets(Hand::ALL.len()).filter(|s| s.len() == my_support.len()) {
+            let Some((my_strategy, value)) = mix_on_support(
+                |my, opponent| matrix[my][opponent],
+                &my_support,
+                &opponent_support,
+            ) else {
+                continue;
+            };
+            let Some((opponent_strategy, _)) = mix_on_support(
+                |opponent, my| matrix[my][opponent],
+                &opponent_support,
+                &my_support,
+            ) else {
+                continue;
+            };
+            let my_payoff = |my: usize| {
+                (0..Hand::ALL.len())
+                    .map(|o| opponent_strategy[o] * matrix[my][o])
+                    .sum::<f64>()
+            };
+            let opponent_payoff = |o: usize| {
+                (0..Hand::ALL.len())
+                    .map(|my| my_strategy[my] * matrix[my][o])
+                    .sum::<f64>()
+            };
+            let stable = (0..Hand::ALL.len()).all(|hand| {
+                my_payoff(hand) <= value + EPSILON && opponent_payoff(hand) >= value - EPSILON
+            });
+            if stable {
+                return Equilibrium {
+                    my_strategy: my_strategy.try_into().unwrap(),
+                    opponent_strategy: opponent_strategy.try_into().unwrap(),
+                    value,
+                };
+            }
+        }
+    }
+    unreachable!("Every finite zero-sum game has an equilibrium")
+}
+
+impl StrategyReport {
+    pub fn new(rounds: &[(Hand, Hand)]) -> Self {
+        let mut opponent_frequencies = [0; 3];
+        for (opponent, _) in rounds {
+            opponent_frequencies[hand_index(opponent)] += 1;
+        }
+        let guide_score = rounds.iter().map(|(opponent, my)| my.play(opponent)).sum();
+
+        let score_against_all =
+            |my: &Hand| -> usize { rounds.iter().map(|(opponent, _)| my.play(opponent)).sum() };
+        let frequency_response = Hand::ALL
+            .into_iter()
+            .max_by_key(|my| score_against_all(my))
+            .unwrap();
+        let best_response_score = rounds
+            .iter()
+            .map(|(opponent, _)| Hand::ALL.iter().map(|my| my.play(opponent)).max().unwrap())
+            .sum();
+
+        let equilibrium = find_equilibrium();
+        let equilibrium_expected_score = rounds
+            .iter()
+            .map(|(opponent, _)| {
+                Hand::ALL
+                    .iter()
+                    .zip(equilibrium.my_strategy)
+                    .map(|(my, probability)| probability * my.play(opponent) as f64)
+                    .sum::<f64>()
+            })
+            .sum();
+
+        Self {
+            rounds: rounds.len(),
+            opponent_frequencies,
+            guide_score,
+            frequency_response,
+            frequency_response_score: score_against_all(&frequency_response),
+            best_response_score,
+            equilibrium,
+            equilibrium_expected_score,
+        }
+    }
+}
+
+impl fmt::Display for StrategyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Rounds: {}", self.rounds)?;
+        for (hand, frequency) in Hand::ALL.iter().zip(self.opponent_frequencies) {
+            writeln!(f, "Opponent plays {:?}: {}", hand, frequency)?;
+        }
+        writeln!(f, "Strategy guide score: {}", self.guide_score)?;
+        writeln!(
+            f,
+            "Frequency response ({:?} every round) score: {}",
+            self.frequency_response, self.frequency_response_score
+        )?;
+        writeln!(
+            f,
+            "Best response per round score: {}",
+            self.best_response_score
+        )?;
+        write!(f, "Equilibrium strategy:")?;
+        for (hand, probability) in Hand::ALL.iter().zip(self.equilibrium.my_strategy) {
+            write!(f, " {:?} {:.3}", hand, probability)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Equilibrium value per round: {:.3}",
+            self.equilibrium.value
+        )?;
+        writeln!(
+            f,
+            "Equilibrium expected score: {:.1}",
+            self.equilibrium_expected_score
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, part_1};
+
+    #[test]
+    fn equilibrium_is_unexploitable() {
+        let equilibrium = find_equilibrium();
+        let matrix = scoring_matrix();
+        assert!((equilibrium.my_strategy.iter().sum::<f64>() - 1.0).abs() < EPSILON);
+        for opponent in 0..Hand::ALL.len() {
+            let expected = equilibrium
+                .my_strategy
+                .iter()
+                .zip(&matrix)
+                .map(|(probability, scores)| probability * scores[opponent])
+                .sum::<f64>();
+            assert!(expected >= equilibrium.value - EPSILON);
+        }
+        assert!((equilibrium.value - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn report_compares_guide_with_best_responses() {
+        let rounds = part_1::parse_data(load_file("./resources/test_data.txt"));
+        let report = StrategyReport::new(&rounds);
+        assert_eq!(report.rounds, 3);
+        assert_eq!(report.opponent_frequencies, [1, 1, 1]);
+        assert_eq!(report.guide_score, 15);
+        assert_eq!(report.frequency_response, Hand::Scissors);
+        assert_eq!(report.frequency_response_score, 18);
+        assert_eq!(report.best_response_score, 24);
+        assert!((report.equilibrium_expected_score - 15.0).abs() < 1e-6);
+    }
+}
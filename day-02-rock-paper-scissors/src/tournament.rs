@@ -0,0 +1,338 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{Hand, RoundResult};
+
+fn winning_hand_against(hand: &Hand) -> Hand {
+    Hand::ALL.into_iter().find(|other| other > hand).unwrap()
+}
+
+fn round_result(my: Hand, opponent: Hand) -> RoundResult {
+    match my.cmp(&opponent) {
+        Ordering::Less => RoundResult::Lose(opponent),
+        Ordering::Equal => RoundResult::Draw(opponent),
+        Ordering::Greater => RoundResult::Win(opponent),
+    }
+}
+
+pub trait Strategy {
+    fn name(&self) -> String;
+    fn next_hand(&mut self) -> Hand;
+    fn observe(&mut self, result: RoundResult);
+    fn reset(&mut self);
+}
+
+pub struct Fixed(pub Hand);
+
+impl Strategy for Fixed {
+    fn name(&self) -> String {
+        format!("Fixed {:?}", self.0)
+    }
+
+    fn next_hand(&mut self) -> Hand {
+        self.0
+    }
+
+    fn observe(&mut self, _result: RoundResult) {}
+
+    fn reset(&mut self) {}
+}
+
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        let seed = seed.max(1);
+        Self { seed, state: seed }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("Random {}", self.seed)
+    }
+
+    fn next_hand(&mut self) -> Hand {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        Hand::ALL[(self.state % Hand::ALL.len() as u64) as usize]
+    }
+
+    fn observe(&mut self, _result: RoundResult) {}
+
+    fn reset(&mut self) {
+        self.state = self.seed;
+    }
+}
+
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "Frequency counter".to_string()
+    }
+
+    fn next_hand(&mut self) -> Hand {
+        let (most_frequent, _) = Hand::ALL
+            .iter()
+            .zip(self.counts)
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .unwrap();
+        winning_hand_against(most_frequent)
+    }
+
+    fn observe(&mut self, result: RoundResult) {
+        let (opponent, _) = result.get_expected_opponent_and_my_hand();
+        self.counts[Hand::ALL.iter().position(|hand| hand == &opponent).unwrap()] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+}
+
+pub struct WinStayLoseShift {
+    first: Hand,
+    current: Hand,
+}
+
+impl WinStayLoseShift {
+    pub fn new(first: Hand) -> Self {
+        Self {
+            first,
+            current: first,
+        }
+    }
+}
+
+impl Strategy for WinStayLoseShift {
+    fn name(&self) -> String {
+        "Win-stay lose-shift".to_string()
+    }
+
+    fn next_hand(&mut self) -> Hand {
+        self.current
+    }
+
+    fn observe(&mut self, result: RoundResult) {
+        if let RoundResult::Lose(opponent) = result {
+            self.current = winning_hand_against(&opponent);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.current = self.first;
+    }
+}
+
+pub struct ReplayGuide {
+    hands: Vec<Hand>,
+    position: usize,
+}
+
+impl ReplayGuide {
+    pub fn new(rounds: &[(Hand, Hand)]) -> Option<Self> {
+        if rounds.is_empty() {
+            return None;
+        }
+        Some(Self {
+            hands: rounds.iter().map(|(_, my)| *my).collect(),
+            position: 0,
+        })
+    }
+}
+
+impl Strategy for ReplayGuide {
+    fn name(&self) -> String {
+        "Strategy guide".to_string()
+    }
+
+    fn next_hand(&mut self) -> Hand {
+        let hand = self.hands[self.position % self.hands.len()];
+        self.position += 1;
+        hand
+    }
+
+    fn observe(&mut self, _result: RoundResult) {}
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub score: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct League {
+    pub standings: Vec<Standing>,
+    pub head_to_head: Vec<Vec<usize>>,
+}
+
+pub fn play_league(bots: &mut [Box<dyn Strategy>], rounds: usize) -> League {
+    let mut standings = bots
+        .iter()
+        .map(|bot| Standing {
+            name: bot.name(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let mut head_to_head = vec![vec![0; bots.len()]; bots.len()];
+
+    for first in 0..bots.len() {
+        for second in (first + 1)..bots.len() {
+            let (left, right) = bots.split_at_mut(second);
+            let (first_bot, second_bot) = (&mut left[first], &mut right[0]);
+            first_bot.reset();
+            second_bot.reset();
+            for _ in 0..rounds {
+                let first_hand = first_bot.next_hand();
+                let second_hand = second_bot.next_hand();
+                let first_result = round_result(first_hand, second_hand);
+                let second_result = round_result(second_hand, first_hand);
+                first_bot.observe(first_result);
+                second_bot.observe(second_result);
+
+                for (player, opponent, result) in [
+                    (first, second, first_result),
+                    (second, first, second_result),
+                ] {
+                    let score = result.play();
+                    head_to_head[player][opponent] += score;
+                    let standing = &mut standings[player];
+                    standing.score += score;
+                    match result {
+                        RoundResult::Win(_) => standing.wins += 1,
+                        RoundResult::Draw(_) => standing.draws += 1,
+                        RoundResult::Lose(_) => standing.losses += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    League {
+        standings,
+        head_to_head,
+    }
+}
+
+impl fmt::Display for League {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self
+            .standings
+            .iter()
+            .map(|standing| standing.name.len())
+            .max()
+            .unwrap_or(0);
+        let mut ranking = (0..self.standings.len()).collect::<Vec<_>>();
+        ranking.sort_by_key(|&bot| std::cmp::Reverse(self.standings[bot].score));
+
+        writeln!(
+            f,
+            "{:>4} {:<name_width$} {:>8} {:>6} {:>6} {:>6}",
+            "#", "bot", "score", "wins", "draws", "losses"
+        )?;
+        for (place, &bot) in ranking.iter().enumerate() {
+            let standing = &self.standings[bot];
+            writeln!(
+                f,
+                "{:>4} {:<name_width$} {:>8} {:>6} {:>6} {:>6}",
+                place + 1,
+                standing.name,
+                standing.score,
+                standing.wins,
+                standing.draws,
+                standing.losses
+            )?;
+        }
+
+        let label_width = name_width + self.standings.len().to_string().len() + 1;
+        writeln!(f)?;
+        write!(f, "{:<label_width$}", "")?;
+        for bot in 0..self.standings.len() {
+            write!(f, " {:>8}", bot + 1)?;
+        }
+        writeln!(f)?;
+        for (bot, scores) in self.head_to_head.iter().enumerate() {
+            write!(
+                f,
+                "{:<label_width$}",
+                format!("{} {}", bot + 1, self.standings[bot].name)
+            )?;
+            for (opponent, score) in scores.iter().enumerate() {
+                if opponent == bot {
+                    write!(f, " {:>8}", "-")?;
+                } else {
+                    write!(f, " {:>8}", score)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, part_1};
+
+    #[test]
+    fn fixed_bots_score_with_hand_play() {
+        let mut bots: Vec<Box<dyn Strategy>> =
+            vec![Box::new(Fixed(Hand::Rock)), Box::new(Fixed(Hand::Paper))];
+        let league = play_league(&mut bots, 10);
+        assert_eq!(
+            league.head_to_head[0][1],
+            10 * Hand::Rock.play(&Hand::Paper)
+        );
+        assert_eq!(
+            league.head_to_head[1][0],
+            10 * Hand::Paper.play(&Hand::Rock)
+        );
+        assert_eq!(league.standings[1].wins, 10);
+        assert_eq!(league.standings[0].losses, 10);
+    }
+
+    #[test]
+    fn adaptive_bots_beat_a_fixed_hand() {
+        let mut bots: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Fixed(Hand::Scissors)),
+            Box::new(FrequencyCounter::default()),
+            Box::new(WinStayLoseShift::new(Hand::Paper)),
+        ];
+        let league = play_league(&mut bots, 100);
+        assert_eq!(league.head_to_head[1][0], 2 + 99 * 7);
+        assert_eq!(league.head_to_head[2][0], 2 + 99 * 7);
+    }
+
+    #[test]
+    fn random_and_guide_bots_are_reproducible() {
+        let rounds = part_1::parse_data(load_file("./resources/test_data.txt"));
+        let mut bots: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Random::new(2022)),
+            Box::new(ReplayGuide::new(&rounds).unwrap()),
+            Box::new(Random::new(7)),
+        ];
+        let first = play_league(&mut bots, 50);
+        let second = play_league(&mut bots, 50);
+        assert_eq!(first, second);
+        let played = first.standings.iter().map(|s| s.wins + s.draws + s.losses);
+        assert!(played.into_iter().all(|rounds| rounds == 100));
+        assert!(ReplayGuide::new(&[]).is_none());
+    }
+}
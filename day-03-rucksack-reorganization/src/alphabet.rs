@@ -0,0 +1,226 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    Parse { line: usize, message: String },
+    DuplicateItem(String),
+    UnknownItem { line: usize, item: String },
+    OddItemCount { line: usize },
+    NoCommonItem { line: usize },
+    SeveralCommonItems { line: usize },
+    InvalidGroupSize(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemFormat {
+    Characters,
+    Separated(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    format: ItemFormat,
+    priorities: HashMap<String, usize>,
+}
+
+impl Alphabet {
+    pub fn load(file_path: &str) -> Result<Self, AlphabetError> {
+        let definition =
+            fs::read_to_string(file_path).expect("Should have been able to read the file");
+        Self::parse(&definition)
+    }
+
+    pub fn parse(definition: &str) -> Result<Self, AlphabetError> {
+        let mut format = ItemFormat::Characters;
+        let mut priorities = HashMap::new();
+        let mut insert = |item: String, priority: usize| {
+            if priorities.insert(item.clone(), priority).is_some() {
+                return Err(AlphabetError::DuplicateItem(item));
+            }
+            Ok(())
+        };
+
+        for (line_idx, line) in definition.lines().enumerate() {
+            let parse_error = |message: &str| AlphabetError::Parse {
+                line: line_idx + 1,
+                message: message.to_string(),
+            };
+            let parse_priority = |priority: &str| {
+                priority
+                    .parse::<usize>()
+                    .map_err(|_| parse_error("priority should be a number"))
+            };
+            let single_char = |item: &str| {
+                let mut chars = item.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(item), None) => Ok(item),
+                    _ => Err(parse_error("range bounds should be single characters")),
+                }
+            };
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match fields.as_slice() {
+                [] => {}
+                ["separator", separator] => format = ItemFormat::Separated(single_char(separator)?),
+                ["range", first, last, priority] => {
+                    let (first, last) = (single_char(first)?, single_char(last)?);
+                    let priority = parse_priority(priority)?;
+                    for (offset, item) in (first..=last).enumerate() {
+                        insert(item.to_string(), priority + offset)?;
+                    }
+                }
+                [item, priority] => insert(item.to_string(), parse_priority(priority)?)?,
+                _ => return Err(parse_error("unsupported definition")),
+            }
+        }
+
+        Ok(Self { format, priorities })
+    }
+
+    pub fn items<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        match self.format {
+            ItemFormat::Characters => line
+                .char_indices()
+                .map(|(idx, item)| &line[idx..idx + item.len_utf8()])
+                .collect(),
+            ItemFormat::Separated(separator) => line
+                .split(separator)
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn priority(&self, item: &str, line: usize) -> Result<usize, AlphabetError> {
+        self.priorities
+            .get(item)
+            .copied()
+            .ok_or_else(|| AlphabetError::UnknownItem {
+                line,
+                item: item.to_string(),
+            })
+    }
+
+    fn single_common_item<'a>(
+        sets: impl IntoIterator<Item = HashSet<&'a str>>,
+        line: usize,
+    ) -> Result<&'a str, AlphabetError> {
+        let common = sets
+            .into_iter()
+            .reduce(|common, set| common.intersection(&set).copied().collect())
+            .unwrap_or_default();
+        match common.len() {
+            0 => Err(AlphabetError::NoCommonItem { line }),
+            1 => Ok(common.into_iter().next().unwrap()),
+            _ => Err(AlphabetError::SeveralCommonItems { line }),
+        }
+    }
+
+    fn rucksacks<'a>(&self, data: &'a str) -> Vec<(usize, Vec<&'a str>)> {
+        data.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_idx, line)| (line_idx + 1, self.items(line.trim())))
+            .collect()
+    }
+
+    pub fn solve_part_1(&self, data: &str) -> Result<usize, AlphabetError> {
+        self.rucksacks(data)
+            .into_iter()
+            .map(|(line, items)| {
+                if items.len() % 2 != 0 {
+                    return Err(AlphabetError::OddItemCount { line });
+                }
+                let (first, second) = items.split_at(items.len() / 2);
+                let sets = [first, second].map(|compartment| compartment.iter().copied().collect());
+                self.priority(Self::single_common_item(sets, line)?, line)
+            })
+            .sum()
+    }
+
+    pub fn solve_part_2(&self, data: &str, group_size: usize) -> Result<usize, AlphabetError> {
+        if group_size == 0 {
+            return Err(AlphabetError::InvalidGroupSize(group_size));
+        }
+        self.rucksacks(data)
+            .chunks(group_size)
+            .map(|group| {
+                let line = group[0].0;
+                let sets = group
+                    .iter()
+                    .map(|(_, items)| items.iter().copied().collect::<HashSet<_>>());
+                self.priority(Self::single_common_item(sets, line)?, line)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_item_priority, load_file, GROUP_SIZE};
+
+    #[test]
+    fn ascii_letters_match_get_item_priority() {
+        let alphabet = Alphabet::load("./resources/ascii_letters.priorities").unwrap();
+        for item in ('a'..='z').chain('A'..='Z') {
+            assert_eq!(
+                alphabet.priority(&item.to_string(), 1),
+                Ok(get_item_priority(item))
+            );
+        }
+        let data = load_file("./resources/test_data.txt");
+        assert_eq!(alphabet.solve_part_1(&data), Ok(157));
+        assert_eq!(alphabet.solve_part_2(&data, GROUP_SIZE), Ok(70));
+    }
+
+    #[test]
+    fn multi_character_item_codes() {
+        let alphabet = Alphabet::load("./resources/sku.priorities").unwrap();
+        let data = load_file("./resources/test_data_sku.txt");
+        assert_eq!(alphabet.solve_part_1(&data), Ok(90));
+        assert_eq!(alphabet.solve_part_2(&data, GROUP_SIZE), Ok(20));
+        assert_eq!(
+            alphabet.solve_part_2(&data, 0),
+            Err(AlphabetError::InvalidGroupSize(0))
+        );
+    }
+
+    #[test]
+    fn unicode_letters_and_digits() {
+        let alphabet = Alphabet::parse("range 0 9 1\nä 11\nß 12\n").unwrap();
+        assert_eq!(alphabet.solve_part_1("ä1ß1\nßä9ß\n"), Ok(14));
+        assert_eq!(
+            alphabet.solve_part_1("0120\nxyxz\n"),
+            Err(AlphabetError::UnknownItem {
+                line: 2,
+                item: "x".to_string()
+            })
+        );
+        assert_eq!(
+            alphabet.solve_part_1("12\n"),
+            Err(AlphabetError::NoCommonItem { line: 1 })
+        );
+        assert_eq!(
+            alphabet.solve_part_1("ää\n123\n"),
+            Err(AlphabetError::OddItemCount { line: 2 })
+        );
+    }
+
+    #[test]
+    fn definition_errors() {
+        assert_eq!(
+            Alphabet::parse("a 1\na 2\n"),
+            Err(AlphabetError::DuplicateItem("a".to_string()))
+        );
+        assert_eq!(
+            Alphabet::parse("range ab z 1\n"),
+            Err(AlphabetError::Parse {
+                line: 1,
+                message: "range bounds should be single characters".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::get_item_priority;
+
+const INVALID_ITEM: u64 = 1;
+
+pub struct BitmaskEngine {
+    priorities: [u8; 256],
+}
+
+impl BitmaskEngine {
+    pub fn new() -> Self {
+        let mut priorities = [0; 256];
+        for byte in u8::MIN..=u8::MAX {
+            if byte.is_ascii_alphabetic() {
+                priorities[byte as usize] = get_item_priority(byte as char) as u8;
+            }
+        }
+        Self { priorities }
+    }
+
+    pub fn mask(&self, items: &[u8]) -> u64 {
+        items
+            .iter()
+            .fold(0, |mask, &item| mask | 1 << self.priorities[item as usize])
+    }
+
+    pub fn priority(mask: u64) -> Option<usize> {
+        if mask.count_ones() != 1 || mask & INVALID_ITEM != 0 {
+            return None;
+        }
+        Some(mask.trailing_zeros() as usize)
+    }
+
+    pub fn compartments_masks(&self, rucksacks: &[&[u8]]) -> Vec<u64> {
+        let (left, right): (Vec<_>, Vec<_>) = rucksacks
+            .iter()
+            .map(|items| {
+                let (left, right) = items.split_at(items.len() / 2);
+                (self.mask(left), self.mask(right))
+            })
+            .unzip();
+        left.iter()
+            .zip(&right)
+            .map(|(left, right)| left & right)
+            .collect()
+    }
+
+    pub fn group_masks(&self, rucksacks: &[&[u8]], group_size: usize) -> Vec<u64> {
+        let masks = rucksacks
+            .iter()
+            .map(|items| self.mask(items))
+            .collect::<Vec<_>>();
+        masks
+            .chunks(group_size)
+            .map(|group| group.iter().fold(u64::MAX, |common, mask| common & mask))
+            .collect()
+    }
+}
+
+impl Default for BitmaskEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rucksacks(data: &str) -> Vec<&[u8]> {
+    data.trim().lines().map(str::as_bytes).collect()
+}
+
+pub fn solve_part_1(data: &str) -> usize {
+    let engine = BitmaskEngine::new();
+    engine
+        .compartments_masks(&rucksacks(data))
+        .into_iter()
+        .map(|mask| BitmaskEngine::priority(mask).unwrap())
+        .sum()
+}
+
+pub fn solve_part_2(data: &str) -> usize {
+    let engine = BitmaskEngine::new();
+    engine
+        .group_masks(&rucksacks(data), crate::GROUP_SIZE)
+        .into_iter()
+        .map(|mask| BitmaskEngine::priority(mask).unwrap())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data, RucksacksGroup, GROUP_SIZE};
+
+    #[test]
+    fn bitmask_engine_matches_hash_set_implementation() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let data = load_file(file_path);
+            let engine = BitmaskEngine::new();
+            let reference = parse_data(data.clone());
+
+            let compartments = engine.compartments_masks(&rucksacks(&data));
+            for (rucksack, mask) in reference.iter().zip(compartments) {
+                assert_eq!(
+                    rucksack
+                        .get_the_same_type_in_compartments()
+                        .map(get_item_priority),
+                    BitmaskEngine::priority(mask)
+                );
+            }
+
+            let groups = engine.group_masks(&rucksacks(&data), GROUP_SIZE);
+            for (chunk, mask) in reference.chunks(GROUP_SIZE).zip(groups) {
+                assert_eq!(
+                    RucksacksGroup::new(chunk)
+                        .get_the_same_type_rucksacks()
+                        .map(get_item_priority),
+                    BitmaskEngine::priority(mask)
+                );
+            }
+
+            assert_eq!(solve_part_1(&data), crate::solve_part_1(file_path));
+            assert_eq!(solve_part_2(&data), crate::solve_part_2(file_path));
+        }
+    }
+
+    #[test]
+    fn ambiguous_or_invalid_items_have_no_priority() {
+        let engine = BitmaskEngine::new();
+        assert_eq!(BitmaskEngine::priority(engine.mask(b"aA")), None);
+        assert_eq!(BitmaskEngine::priority(engine.mask(b"")), None);
+        assert_eq!(BitmaskEngine::priority(engine.mask(b"1")), None);
+        assert_eq!(BitmaskEngine::priority(engine.mask(b"zz")), Some(26));
+    }
+}
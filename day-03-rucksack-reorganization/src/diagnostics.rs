@@ -0,0 +1,297 @@
+use std::{fmt, num::NonZeroUsize};
+
+use crate::{Rucksack, RucksacksGroup};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    Fixed(NonZeroUsize),
+    Delimited,
+}
+
+impl Grouping {
+    pub fn parse(argument: &str) -> Option<Self> {
+        match argument {
+            "delimited" => Some(Self::Delimited),
+            size => size.parse().ok().map(Self::Fixed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    Rucksack { line: usize },
+    Group { lines: Vec<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    OddLength(usize),
+    NoCommonItem,
+    SeveralCommonItems(Vec<char>),
+    IncompleteGroup { expected: usize, found: usize },
+    UnknownItem(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub subject: Subject,
+    pub issue: Issue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub rucksacks: usize,
+    pub groups: usize,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+pub fn common_items_issue(common_items: Vec<char>) -> Option<Issue> {
+    match common_items.len() {
+        0 => Some(Issue::NoCommonItem),
+        1 => None,
+        _ => Some(Issue::SeveralCommonItems(common_items)),
+    }
+}
+
+pub fn parse_lines(data: &str) -> Vec<Option<(usize, Rucksack)>> {
+    data.lines()
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let line = line.trim();
+            (!line.is_empty()).then(|| (line_idx + 1, Rucksack::new(line)))
+        })
+        .collect()
+}
+
+pub fn group_lines(
+    lines: Vec<Option<(usize, Rucksack)>>,
+    grouping: Grouping,
+) -> Vec<Vec<(usize, Rucksack)>> {
+    match grouping {
+        Grouping::Fixed(group_size) => lines
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(group_size.get())
+            .map(<[_]>::to_vec)
+            .collect(),
+        Grouping::Delimited => lines
+            .split(Option::is_none)
+            .filter(|group| !group.is_empty())
+            .map(|group| group.iter().flatten().cloned().collect())
+            .collect(),
+    }
+}
+
+pub fn validate(data: &str, grouping: Grouping) -> ValidationReport {
+    let lines = parse_lines(data);
+    let mut diagnostics = Vec::new();
+
+    for (line, rucksack) in lines.iter().flatten() {
+        let subject = || Subject::Rucksack { line: *line };
+        let length = rucksack.items.chars().count();
+        if length % 2 != 0 {
+            diagnostics.push(Diagnostic {
+                subject: subject(),
+                issue: Issue::OddLength(length),
+            });
+        }
+        if let Some(item) = rucksack
+            .items
+            .chars()
+            .find(|item| !item.is_ascii_alphabetic())
+        {
+            diagnostics.push(Diagnostic {
+                subject: subject(),
+                issue: Issue::UnknownItem(item),
+            });
+        }
+        if let Some(issue) = common_items_issue(rucksack.get_common_types_in_compartments()) {
+            diagnostics.push(Diagnostic {
+                subject: subject(),
+                issue,
+            });
+        }
+    }
+
+    let rucksacks = lines.iter().flatten().count();
+    let groups = group_lines(lines, grouping);
+    for group in &groups {
+        let subject = || Subject::Group {
+            lines: group.iter().map(|(line, _)| *line).collect(),
+        };
+        if let Grouping::Fixed(expected) = grouping {
+            let expected = expected.get();
+            if group.len() != expected {
+                diagnostics.push(Diagnostic {
+                    subject: subject(),
+                    issue: Issue::IncompleteGroup {
+                        expected,
+                        found: group.len(),
+                    },
+                });
+            }
+        }
+        let rucksacks = group
+            .iter()
+            .map(|(_, rucksack)| rucksack.clone())
+            .collect::<Vec<_>>();
+        if let Some(issue) = common_items_issue(RucksacksGroup::new(&rucksacks).get_common_types())
+        {
+            diagnostics.push(Diagnostic {
+                subject: subject(),
+                issue,
+            });
+        }
+    }
+
+    ValidationReport {
+        rucksacks,
+        groups: groups.len(),
+        diagnostics,
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rucksack { line } => write!(f, "rucksack at line {}", line),
+            Self::Group { lines } => {
+                let lines = lines.iter().map(usize::to_string).collect::<Vec<_>>();
+                write!(f, "group at lines {}", lines.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddLength(length) => write!(f, "odd number of items ({})", length),
+            Self::NoCommonItem => write!(f, "no common item"),
+            Self::SeveralCommonItems(items) => {
+                write!(
+                    f,
+                    "several common items ({})",
+                    items.iter().collect::<String>()
+                )
+            }
+            Self::IncompleteGroup { expected, found } => {
+                write!(f, "{} rucksacks instead of {}", found, expected)
+            }
+            Self::UnknownItem(item) => write!(f, "item {:?} has no priority", item),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Checked {} rucksacks in {} groups, found {} issues",
+            self.rucksacks,
+            self.groups,
+            self.diagnostics.len()
+        )?;
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}: {}", diagnostic.subject, diagnostic.issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_file;
+
+    #[test]
+    fn valid_input_has_no_diagnostics() {
+        let data = load_file("./resources/test_data.txt");
+        let report = validate(&data, Grouping::parse("3").unwrap());
+        assert_eq!(report.rucksacks, 6);
+        assert_eq!(report.groups, 2);
+        assert_eq!(report.diagnostics, vec![]);
+    }
+
+    #[test]
+    fn report_lists_problems_with_line_numbers() {
+        let data = "abcA\nabcdb\nabab\n\nxyzx\nXYZz\n";
+        let report = validate(data, Grouping::Delimited);
+        assert_eq!(report.groups, 2);
+        assert_eq!(
+            report.diagnostics,
+            vec![
+                Diagnostic {
+                    subject: Subject::Rucksack { line: 1 },
+                    issue: Issue::NoCommonItem,
+                },
+                Diagnostic {
+                    subject: Subject::Rucksack { line: 2 },
+                    issue: Issue::OddLength(5),
+                },
+                Diagnostic {
+                    subject: Subject::Rucksack { line: 3 },
+                    issue: Issue::SeveralCommonItems(vec!['a', 'b']),
+                },
+                Diagnostic {
+                    subject: Subject::Rucksack { line: 6 },
+                    issue: Issue::NoCommonItem,
+                },
+                Diagnostic {
+                    subject: Subject::Group {
+                        lines: vec![1, 2, 3]
+                    },
+                    issue: Issue::SeveralCommonItems(vec!['a', 'b']),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_ascii_items_are_reported() {
+        let report = validate("aébé\nxyzx\n", Grouping::parse("2").unwrap());
+        assert_eq!(
+            report.diagnostics,
+            vec![
+                Diagnostic {
+                    subject: Subject::Rucksack { line: 1 },
+                    issue: Issue::UnknownItem('é'),
+                },
+                Diagnostic {
+                    subject: Subject::Group { lines: vec![1, 2] },
+                    issue: Issue::NoCommonItem,
+                },
+            ]
+        );
+        assert_eq!(Grouping::parse("0"), None);
+        assert_eq!(
+            Grouping::parse("4"),
+            NonZeroUsize::new(4).map(Grouping::Fixed)
+        );
+        assert_eq!(Grouping::parse("delimited"), Some(Grouping::Delimited));
+    }
+
+    #[test]
+    fn fixed_groups_of_any_size() {
+        let data = load_file("./resources/test_data.txt");
+        let report = validate(&data, Grouping::parse("4").unwrap());
+        assert_eq!(report.groups, 2);
+        assert_eq!(
+            report.diagnostics[0],
+            Diagnostic {
+                subject: Subject::Group {
+                    lines: vec![1, 2, 3, 4]
+                },
+                issue: Issue::NoCommonItem,
+            }
+        );
+        assert_eq!(
+            report.diagnostics[1].issue,
+            Issue::IncompleteGroup {
+                expected: 4,
+                found: 2
+            }
+        );
+    }
+}
@@ -1,17 +1,24 @@
-use std::{collections::HashSet, fs};
+mod alphabet;
+mod bitmask;
+mod diagnostics;
+
+use std::{collections::HashSet, env, fs, num::NonZeroUsize};
+
+use alphabet::Alphabet;
+use diagnostics::{Diagnostic, Grouping, Issue, Subject};
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
 
 fn parse_data(string_data: String) -> Vec<Rucksack> {
-    string_data
-        .trim()
-        .lines()
-        .map(|line| Rucksack::new(line))
-        .collect()
+    string_data.trim().lines().map(Rucksack::new).collect()
 }
 
+const GROUP_SIZE: usize = 3;
+
+const GROUPING: Grouping = Grouping::Fixed(NonZeroUsize::new(GROUP_SIZE).unwrap());
+
 pub fn get_item_priority(item: char) -> usize {
     match item {
         'a'..='z' => item as usize - 'a' as usize + 1,
@@ -32,38 +39,46 @@ impl Rucksack {
         }
     }
 
-    pub fn get_the_same_type_in_compartments(&self) -> Option<char> {
-        let (item1, item2) = self.items.split_at(self.items.len() / 2);
-        let common_letters = item1
+    pub fn get_common_types_in_compartments(&self) -> Vec<char> {
+        let middle = self
+            .items
+            .char_indices()
+            .nth(self.items.chars().count() / 2)
+            .map_or(self.items.len(), |(idx, _)| idx);
+        let (item1, item2) = self.items.split_at(middle);
+        let mut common_letters = item1
             .chars()
             .collect::<HashSet<_>>()
             .intersection(&item2.chars().collect::<HashSet<_>>())
             .cloned()
             .collect::<Vec<_>>();
-        if common_letters.len() != 1 {
-            return None;
-        }
-        Some(common_letters[0])
+        common_letters.sort();
+        common_letters
+    }
+
+    pub fn get_the_same_type_in_compartments(&self) -> Option<char> {
+        single(self.get_common_types_in_compartments())
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct RucksacksGroup<'a> {
-    rucksacks: &'a [Rucksack; 3],
+    rucksacks: &'a [Rucksack],
 }
 
 impl<'a> RucksacksGroup<'a> {
-    pub fn new(rucksacks: &'a [Rucksack; 3]) -> Self {
+    pub fn new(rucksacks: &'a [Rucksack]) -> Self {
         Self { rucksacks }
     }
 
-    pub fn get_the_same_type_rucksacks(&self) -> Option<char> {
-        let common_letters = self
-            .rucksacks
+    pub fn get_common_types(&self) -> Vec<char> {
+        let Some((first, rest)) = self.rucksacks.split_first() else {
+            return Vec::new();
+        };
+        let mut common_letters = rest
             .iter()
-            .skip(1)
             .fold(
-                self.rucksacks[0].items.chars().collect::<HashSet<_>>(),
+                first.items.chars().collect::<HashSet<_>>(),
                 |intersection, rucksack| {
                     intersection
                         .intersection(&rucksack.items.chars().collect::<HashSet<_>>())
@@ -73,11 +88,20 @@ impl<'a> RucksacksGroup<'a> {
             )
             .into_iter()
             .collect::<Vec<_>>();
-        if common_letters.len() != 1 {
-            return None;
-        }
-        Some(common_letters[0])
+        common_letters.sort();
+        common_letters
     }
+
+    pub fn get_the_same_type_rucksacks(&self) -> Option<char> {
+        single(self.get_common_types())
+    }
+}
+
+fn single(common_letters: Vec<char>) -> Option<char> {
+    if common_letters.len() != 1 {
+        return None;
+    }
+    Some(common_letters[0])
 }
 
 pub fn solve_part_1(file_path: &str) -> usize {
@@ -90,16 +114,31 @@ pub fn solve_part_1(file_path: &str) -> usize {
 }
 
 pub fn solve_part_2(file_path: &str) -> usize {
+    solve_part_2_grouped(file_path, GROUPING)
+        .unwrap_or_else(|diagnostic| panic!("Invalid {}: {}", diagnostic.subject, diagnostic.issue))
+}
+
+pub fn solve_part_2_grouped(file_path: &str, grouping: Grouping) -> Result<usize, Diagnostic> {
     let data = load_file(file_path);
-    let rounds = parse_data(data);
-    rounds
-        .chunks(3)
-        .map(|chunk| {
-            get_item_priority(
-                RucksacksGroup::new(chunk.try_into().unwrap())
-                    .get_the_same_type_rucksacks()
-                    .unwrap(),
-            )
+    let groups = diagnostics::group_lines(diagnostics::parse_lines(&data), grouping);
+    groups
+        .into_iter()
+        .map(|group| {
+            let subject = Subject::Group {
+                lines: group.iter().map(|(line, _)| *line).collect(),
+            };
+            let rucksacks = group
+                .into_iter()
+                .map(|(_, rucksack)| rucksack)
+                .collect::<Vec<_>>();
+            let group = RucksacksGroup::new(&rucksacks);
+            let issue = match group.get_the_same_type_rucksacks() {
+                Some(item) if item.is_ascii_alphabetic() => return Ok(get_item_priority(item)),
+                Some(item) => Issue::UnknownItem(item),
+                None => diagnostics::common_items_issue(group.get_common_types())
+                    .expect("A group without a single common item should have an issue"),
+            };
+            Err(Diagnostic { subject, issue })
         })
         .sum()
 }
@@ -114,10 +153,69 @@ fn part_2(file_path: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn bitmask(file_path: &str) {
+    let data = load_file(file_path);
+    println!("Part 1 result: {:?}", bitmask::solve_part_1(&data));
+    println!("Part 2 result: {:?}", bitmask::solve_part_2(&data));
+}
+
+fn validate(file_path: &str, grouping: Grouping) {
+    let data = load_file(file_path);
+    print!("{}", diagnostics::validate(&data, grouping));
+}
+
+fn with_alphabet(alphabet_path: &str, file_path: &str) {
+    let alphabet = match Alphabet::load(alphabet_path) {
+        Ok(alphabet) => alphabet,
+        Err(error) => {
+            println!("Invalid alphabet: {:?}", error);
+            return;
+        }
+    };
+    let data = load_file(file_path);
+    println!("Part 1 result: {:?}", alphabet.solve_part_1(&data));
+    println!(
+        "Part 2 result: {:?}",
+        alphabet.solve_part_2(&data, GROUP_SIZE)
+    );
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    match env::args().nth(1).as_deref() {
+        Some("bitmask") => bitmask(FILE_PATH),
+        Some("alphabet") => {
+            let alphabet_path = env::args()
+                .nth(2)
+                .unwrap_or("./resources/ascii_letters.priorities".to_string());
+            let file_path = env::args().nth(3).unwrap_or(FILE_PATH.to_string());
+            with_alphabet(&alphabet_path, &file_path);
+        }
+        Some(command @ ("validate" | "groups")) => {
+            let grouping = match env::args().nth(2) {
+                None => GROUPING,
+                Some(argument) => match Grouping::parse(&argument) {
+                    Some(grouping) => grouping,
+                    None => {
+                        println!("Group size should be a positive number or \"delimited\"");
+                        return;
+                    }
+                },
+            };
+            if command == "validate" {
+                validate(FILE_PATH, grouping);
+            } else {
+                match solve_part_2_grouped(FILE_PATH, grouping) {
+                    Ok(result) => println!("Part 2 result: {:?}", result),
+                    Err(diagnostic) => println!("{}: {}", diagnostic.subject, diagnostic.issue),
+                }
+            }
+        }
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +288,24 @@ mod tests {
         let result = solve_part_2("./resources/test_data.txt");
         assert_eq!(result, 70);
     }
+
+    #[test]
+    fn part_2_with_other_groupings() {
+        let result =
+            solve_part_2_grouped("./resources/test_data_delimited.txt", Grouping::Delimited);
+        assert_eq!(result, Ok(70));
+        assert_eq!(
+            solve_part_2_grouped("./resources/test_data.txt", Grouping::parse("6").unwrap()),
+            Err(Diagnostic {
+                subject: Subject::Group {
+                    lines: vec![1, 2, 3, 4, 5, 6]
+                },
+                issue: Issue::NoCommonItem
+            })
+        );
+        assert_eq!(
+            Rucksack::new("aéb€a").get_common_types_in_compartments(),
+            vec!['a']
+        );
+    }
 }
@@ -0,0 +1,178 @@
+use std::{fmt, iter};
+
+use crate::{sweep, ElvesPair, Range};
+
+const HISTOGRAM_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    Nobody,
+    Once,
+    Multiple,
+}
+
+impl Coverage {
+    fn from_count(count: usize) -> Self {
+        match count {
+            0 => Self::Nobody,
+            1 => Self::Once,
+            _ => Self::Multiple,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageError {
+    ReversedCamp(Range),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub camp: Range,
+    pub runs: Vec<(Range, usize)>,
+}
+
+fn sections_in(range: &Range) -> usize {
+    range.1 - range.0 + 1
+}
+
+impl CoverageReport {
+    pub fn new(elves_pair: &[ElvesPair], camp: Range) -> Result<Self, CoverageError> {
+        if camp.0 > camp.1 {
+            return Err(CoverageError::ReversedCamp(camp));
+        }
+        let clipped = elves_pair
+            .iter()
+            .flat_map(|pair| [pair.0, pair.1])
+            .filter(|range| range.overlap(&camp))
+            .map(|range| Range(range.0.max(camp.0), range.1.min(camp.1)));
+        let mut runs = Vec::new();
+        let mut next = camp.0;
+        for segment in sweep::coverage(clipped) {
+            if segment.range.0 > next {
+                runs.push((Range(next, segment.range.0 - 1), 0));
+            }
+            runs.push((segment.range, segment.elves));
+            next = segment.range.1 + 1;
+        }
+        if next <= camp.1 {
+            runs.push((Range(next, camp.1), 0));
+        }
+        Ok(Self { camp, runs })
+    }
+
+    pub fn counts(&self) -> Vec<usize> {
+        self.runs
+            .iter()
+            .flat_map(|(range, count)| iter::repeat_n(*count, sections_in(range)))
+            .collect()
+    }
+
+    pub fn coverage_runs(&self) -> Vec<(Range, Coverage)> {
+        let mut runs: Vec<(Range, Coverage)> = Vec::new();
+        for &(range, count) in &self.runs {
+            let coverage = Coverage::from_count(count);
+            match runs.last_mut() {
+                Some((last, last_coverage)) if *last_coverage == coverage => last.1 = range.1,
+                _ => runs.push((range, coverage)),
+            }
+        }
+        runs
+    }
+
+    pub fn sections(&self, coverage: Coverage) -> usize {
+        self.runs
+            .iter()
+            .filter(|(_, count)| Coverage::from_count(*count) == coverage)
+            .map(|(range, _)| sections_in(range))
+            .sum()
+    }
+
+    pub fn histogram(&self) -> String {
+        let counts = self.counts();
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+        let label_width = self.camp.1.to_string().len();
+        counts
+            .iter()
+            .enumerate()
+            .map(|(offset, &count)| {
+                let bar = (count * HISTOGRAM_WIDTH).div_ceil(max);
+                format!(
+                    "{:>label_width$} | {} {}\n",
+                    self.camp.0 + offset,
+                    "#".repeat(bar),
+                    count
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Camp sections {}", self.camp)?;
+        for coverage in [Coverage::Nobody, Coverage::Once, Coverage::Multiple] {
+            writeln!(f, "{:?}: {} sections", coverage, self.sections(coverage))?;
+        }
+        writeln!(f, "Coverage intervals:")?;
+        for (range, coverage) in self.coverage_runs() {
+            writeln!(f, "  {} {:?}", range, coverage)?;
+        }
+        writeln!(f, "Elves per section:")?;
+        for (range, count) in &self.runs {
+            writeln!(f, "  {} {}", range, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    #[test]
+    fn coverage_counts_and_intervals() {
+        let elves_pair = parse_data(load_file("./resources/test_data.txt"));
+        let report = CoverageReport::new(&elves_pair, Range(1, 10)).unwrap();
+        assert_eq!(report.counts(), vec![0, 4, 5, 7, 7, 8, 6, 4, 1, 0]);
+        assert_eq!(
+            report.coverage_runs(),
+            vec![
+                (Range(1, 1), Coverage::Nobody),
+                (Range(2, 8), Coverage::Multiple),
+                (Range(9, 9), Coverage::Once),
+                (Range(10, 10), Coverage::Nobody),
+            ]
+        );
+        assert_eq!(report.runs[3], (Range(4, 5), 7));
+        assert_eq!(report.sections(Coverage::Nobody), 2);
+        assert_eq!(report.sections(Coverage::Once), 1);
+        assert_eq!(report.sections(Coverage::Multiple), 7);
+    }
+
+    #[test]
+    fn camp_range_clips_assignments() {
+        let elves_pair = parse_data(load_file("./resources/test_data.txt"));
+        let report = CoverageReport::new(&elves_pair, Range(8, 12)).unwrap();
+        assert_eq!(report.counts(), vec![4, 1, 0, 0, 0]);
+        assert_eq!(
+            report.runs,
+            vec![(Range(8, 8), 4), (Range(9, 9), 1), (Range(10, 12), 0)]
+        );
+        let histogram = report.histogram();
+        let lines = histogram.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], format!(" 8 | {} 4", "#".repeat(HISTOGRAM_WIDTH)));
+        assert_eq!(lines[1], format!(" 9 | {} 1", "#".repeat(15)));
+        assert_eq!(lines[4], "12 |  0");
+    }
+
+    #[test]
+    fn reversed_camp_is_rejected() {
+        let elves_pair = parse_data(load_file("./resources/test_data.txt"));
+        assert_eq!(
+            CoverageReport::new(&elves_pair, Range(20, 10)),
+            Err(CoverageError::ReversedCamp(Range(20, 10)))
+        );
+    }
+}
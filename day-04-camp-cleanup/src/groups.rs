@@ -0,0 +1,248 @@
+use std::fmt;
+
+use crate::{sweep, Range};
+
+const MAX_EXACT_ELVES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElvesGroup(pub Vec<Range>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupReport {
+    pub line: usize,
+    pub redundant: Vec<usize>,
+    pub gaps: Vec<Range>,
+    pub greedy_cover: Vec<usize>,
+    pub exact_cover: Option<Vec<usize>>,
+}
+
+fn parse_range(range: &str) -> Range {
+    let (start, end) = range.trim().split_once('-').unwrap();
+    Range(start.parse().unwrap(), end.parse().unwrap())
+}
+
+pub fn parse_groups(string_data: String) -> Vec<ElvesGroup> {
+    string_data
+        .trim()
+        .lines()
+        .map(|line| ElvesGroup(line.split(',').map(parse_range).collect()))
+        .collect()
+}
+
+fn union(ranges: impl IntoIterator<Item = Range>) -> Vec<Range> {
+    let mut ranges = ranges.into_iter().collect::<Vec<_>>();
+    ranges.sort_unstable();
+    let mut merged: Vec<Range> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.0 <= last.1 + 1 => last.1 = last.1.max(range.1),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl ElvesGroup {
+    pub fn sections(&self) -> Vec<Range> {
+        union(self.0.iter().copied())
+    }
+
+    pub fn redundant_elves(&self) -> Vec<usize> {
+        let shared = union(
+            sweep::coverage(self.0.iter().copied())
+                .into_iter()
+                .filter(|segment| segment.elves > 1)
+                .map(|segment| segment.range),
+        );
+        (0..self.0.len())
+            .filter(|&elf| shared.iter().any(|range| self.0[elf].in_other(range)))
+            .collect()
+    }
+
+    pub fn gaps(&self) -> Vec<Range> {
+        self.sections()
+            .windows(2)
+            .map(|pair| Range(pair[0].1 + 1, pair[1].0 - 1))
+            .collect()
+    }
+
+    pub fn greedy_cover(&self) -> Vec<usize> {
+        let mut order = (0..self.0.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&elf| self.0[elf]);
+
+        let mut cover = Vec::new();
+        let mut next = 0;
+        for section in self.sections() {
+            let mut position = section.0;
+            while position <= section.1 {
+                let mut best: Option<usize> = None;
+                while next < order.len() && self.0[order[next]].0 <= position {
+                    let elf = order[next];
+                    if best.is_none_or(|best| self.0[elf].1 > self.0[best].1) {
+                        best = Some(elf);
+                    }
+                    next += 1;
+                }
+                let best = best.unwrap();
+                cover.push(best);
+                position = self.0[best].1 + 1;
+            }
+        }
+        cover.sort_unstable();
+        cover
+    }
+
+    fn search_cover(
+        &self,
+        target: &[Range],
+        size: usize,
+        first: usize,
+        chosen: &mut Vec<usize>,
+    ) -> bool {
+        if chosen.len() == size {
+            return union(chosen.iter().map(|&elf| self.0[elf])) == target;
+        }
+        for elf in first..self.0.len() {
+            chosen.push(elf);
+            if self.search_cover(target, size, elf + 1, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+        false
+    }
+
+    pub fn exact_cover(&self) -> Option<Vec<usize>> {
+        if self.0.len() > MAX_EXACT_ELVES {
+            return None;
+        }
+        let target = self.sections();
+        (0..=self.0.len()).find_map(|size| {
+            let mut chosen = Vec::new();
+            self.search_cover(&target, size, 0, &mut chosen)
+                .then_some(chosen)
+        })
+    }
+}
+
+pub fn analyse(groups: &[ElvesGroup]) -> Vec<GroupReport> {
+    groups
+        .iter()
+        .enumerate()
+        .map(|(line_idx, group)| GroupReport {
+            line: line_idx + 1,
+            redundant: group.redundant_elves(),
+            gaps: group.gaps(),
+            greedy_cover: group.greedy_cover(),
+            exact_cover: group.exact_cover(),
+        })
+        .collect()
+}
+
+fn join<T: fmt::Display>(items: impl IntoIterator<Item = T>) -> String {
+    let items = items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+impl fmt::Display for GroupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elves = |elves: &[usize]| join(elves.iter().map(|elf| elf + 1));
+        write!(
+            f,
+            "line {}: redundant elves {}; gaps {}; greedy cover {}",
+            self.line,
+            elves(&self.redundant),
+            join(&self.gaps),
+            elves(&self.greedy_cover)
+        )?;
+        match &self.exact_cover {
+            Some(cover) => write!(f, "; exact cover {}", elves(cover)),
+            None => write!(f, "; exact cover skipped"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    #[test]
+    fn parse_groups_of_any_size() {
+        let groups = parse_groups(load_file("./resources/test_data_groups.txt"));
+        assert_eq!(
+            groups.iter().map(|group| group.0.len()).collect::<Vec<_>>(),
+            vec![2, 3, 5, 4, 3]
+        );
+        let pairs = parse_data(load_file("./resources/test_data.txt"));
+        let groups = parse_groups(load_file("./resources/test_data.txt"));
+        for (pair, group) in pairs.iter().zip(&groups) {
+            assert_eq!(group.0, vec![pair.0, pair.1]);
+        }
+    }
+
+    #[test]
+    fn redundant_elves_and_gaps() {
+        let reports = analyse(&parse_groups(load_file("./resources/test_data_groups.txt")));
+        let redundant = reports
+            .iter()
+            .map(|report| report.redundant.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            redundant,
+            vec![vec![], vec![1], vec![1, 2, 4], vec![2], vec![0, 1, 2]]
+        );
+        let gaps = reports
+            .iter()
+            .map(|report| report.gaps.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gaps,
+            vec![
+                vec![Range(5, 5)],
+                vec![],
+                vec![],
+                vec![Range(26, 29)],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    fn greedy_cover_is_minimal() {
+        let groups = parse_groups(load_file("./resources/test_data_groups.txt"));
+        let greedy = groups
+            .iter()
+            .map(ElvesGroup::greedy_cover)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            greedy,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 2, 3],
+                vec![0, 1, 3],
+                vec![0]
+            ]
+        );
+        for group in groups
+            .iter()
+            .chain(&parse_groups(load_file("./resources/puzzle.txt")))
+        {
+            let greedy = group.greedy_cover();
+            let exact = group.exact_cover().unwrap();
+            assert_eq!(greedy.len(), exact.len());
+            assert_eq!(
+                union(exact.iter().map(|&elf| group.0[elf])),
+                group.sections()
+            );
+        }
+    }
+}
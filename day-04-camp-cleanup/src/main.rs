@@ -1,4 +1,8 @@
-use std::fs;
+use std::{env, fs};
+
+mod coverage;
+mod groups;
+mod sweep;
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
@@ -71,10 +75,69 @@ fn part_2(file_path: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn roster(file_path: &str) {
+    let data = load_file(file_path);
+    let elves_pair = parse_data(data);
+    print!("{}", sweep::sweep(&sweep::assignments(&elves_pair)));
+}
+
+fn groups(file_path: &str) {
+    let data = load_file(file_path);
+    for report in groups::analyse(&groups::parse_groups(data)) {
+        println!("{}", report);
+    }
+}
+
+fn camp_coverage(file_path: &str, camp: Option<String>, histogram: bool) {
+    let data = load_file(file_path);
+    let elves_pair = parse_data(data);
+    let camp = match camp {
+        Some(camp) => {
+            let (start, end) = camp
+                .split_once('-')
+                .expect("Camp should be a start-end range");
+            Range(start.parse().unwrap(), end.parse().unwrap())
+        }
+        None => Range(
+            elves_pair
+                .iter()
+                .map(|pair| pair.0 .0.min(pair.1 .0))
+                .min()
+                .unwrap_or(0),
+            elves_pair
+                .iter()
+                .map(|pair| pair.0 .1.max(pair.1 .1))
+                .max()
+                .unwrap_or(0),
+        ),
+    };
+    let report = match coverage::CoverageReport::new(&elves_pair, camp) {
+        Ok(report) => report,
+        Err(error) => {
+            println!("Invalid camp: {:?}", error);
+            return;
+        }
+    };
+    if histogram {
+        print!("{}", report.histogram());
+    } else {
+        print!("{}", report);
+    }
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    match env::args().nth(1).as_deref() {
+        Some("roster") => roster(FILE_PATH),
+        Some(command @ ("coverage" | "histogram")) => {
+            camp_coverage(FILE_PATH, env::args().nth(2), command == "histogram")
+        }
+        Some("groups") => groups(&env::args().nth(2).unwrap_or(FILE_PATH.to_string())),
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,269 @@
+use std::{cmp::Reverse, collections::BTreeSet, fmt};
+
+use crate::{ElvesPair, Range};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Assignment {
+    pub line: usize,
+    pub elf: usize,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub range: Range,
+    pub elves: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepReport {
+    pub overlaps: Vec<(Assignment, Assignment)>,
+    pub containments: Vec<(Assignment, Assignment)>,
+    pub coverage: Vec<Segment>,
+    pub max_coverage: usize,
+}
+
+pub fn assignments(pairs: &[ElvesPair]) -> Vec<Assignment> {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(line_idx, pair)| {
+            [pair.0, pair.1]
+                .into_iter()
+                .enumerate()
+                .map(move |(elf_idx, range)| Assignment {
+                    line: line_idx + 1,
+                    elf: elf_idx + 1,
+                    range,
+                })
+        })
+        .collect()
+}
+
+pub fn coverage(ranges: impl IntoIterator<Item = Range>) -> Vec<Segment> {
+    let mut events = ranges
+        .into_iter()
+        .flat_map(|range| [(range.0, 1), (range.1 + 1, -1)])
+        .collect::<Vec<(usize, isize)>>();
+    events.sort_unstable();
+
+    let mut segments = Vec::new();
+    let mut elves = 0;
+    for (idx, &(section, delta)) in events.iter().enumerate() {
+        elves += delta;
+        let next = events.get(idx + 1).map(|(next, _)| *next);
+        let Some(next) = next.filter(|next| elves > 0 && *next > section) else {
+            continue;
+        };
+        match segments.last_mut() {
+            Some(Segment { range, elves: last })
+                if range.1 + 1 == section && *last == elves as usize =>
+            {
+                range.1 = next - 1;
+            }
+            _ => segments.push(Segment {
+                range: Range(section, next - 1),
+                elves: elves as usize,
+            }),
+        }
+    }
+    segments
+}
+
+pub fn sweep(assignments: &[Assignment]) -> SweepReport {
+    let mut order = (0..assignments.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&idx| {
+        let assignment = &assignments[idx];
+        (assignment.range.0, Reverse(assignment.range.1), idx)
+    });
+
+    let mut overlaps = Vec::new();
+    let mut containments = Vec::new();
+    let mut active = BTreeSet::<(usize, usize)>::new();
+    for idx in order {
+        let current = assignments[idx];
+        while let Some(&(end, ended)) = active.first() {
+            if end >= current.range.0 {
+                break;
+            }
+            active.remove(&(end, ended));
+        }
+        for &(end, other) in &active {
+            let other = assignments[other];
+            if other.line == current.line {
+                continue;
+            }
+            let (first, second) = if other < current {
+                (other, current)
+            } else {
+                (current, other)
+            };
+            overlaps.push((first, second));
+            if end >= current.range.1 {
+                containments.push((other, current));
+            }
+        }
+        active.insert((current.range.1, idx));
+    }
+    overlaps.sort_unstable();
+    containments.sort_unstable();
+
+    let coverage = coverage(assignments.iter().map(|assignment| assignment.range));
+    let max_coverage = coverage
+        .iter()
+        .map(|segment| segment.elves)
+        .max()
+        .unwrap_or(0);
+    SweepReport {
+        overlaps,
+        containments,
+        coverage,
+        max_coverage,
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.0, self.1)
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} elf {} ({})", self.line, self.elf, self.range)
+    }
+}
+
+impl fmt::Display for SweepReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Overlapping assignments: {}", self.overlaps.len())?;
+        for (first, second) in &self.overlaps {
+            writeln!(f, "  {} overlaps {}", first, second)?;
+        }
+        writeln!(f, "Contained assignments: {}", self.containments.len())?;
+        for (outer, inner) in &self.containments {
+            writeln!(f, "  {} contains {}", outer, inner)?;
+        }
+        writeln!(f, "Maximum coverage: {} elves", self.max_coverage)?;
+        for segment in &self.coverage {
+            if segment.elves == self.max_coverage {
+                writeln!(f, "  sections {}", segment.range)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    fn brute_force(assignments: &[Assignment]) -> SweepReport {
+        let mut overlaps = Vec::new();
+        let mut containments = Vec::new();
+        for (idx, first) in assignments.iter().enumerate() {
+            for second in &assignments[idx + 1..] {
+                if first.line == second.line {
+                    continue;
+                }
+                if first.range.overlap(&second.range) {
+                    overlaps.push((*first, *second));
+                }
+                if second.range.in_other(&first.range) {
+                    containments.push((*first, *second));
+                } else if first.range.in_other(&second.range) {
+                    containments.push((*second, *first));
+                }
+            }
+        }
+        overlaps.sort_unstable();
+        containments.sort_unstable();
+        let max_coverage = (0..=100)
+            .map(|section| {
+                assignments
+                    .iter()
+                    .filter(|a| a.range.0 <= section && section <= a.range.1)
+                    .count()
+            })
+            .max()
+            .unwrap();
+        SweepReport {
+            overlaps,
+            containments,
+            coverage: vec![],
+            max_coverage,
+        }
+    }
+
+    #[test]
+    fn sweep_reports_pairs_across_lines() {
+        let pairs = parse_data(load_file("./resources/test_data.txt"));
+        let report = sweep(&assignments(&pairs));
+        let contains = |outer: (usize, usize), inner: (usize, usize)| {
+            let find = |(line, elf)| Assignment {
+                line,
+                elf,
+                range: if elf == 1 {
+                    pairs[line - 1].0
+                } else {
+                    pairs[line - 1].1
+                },
+            };
+            report.containments.contains(&(find(outer), find(inner)))
+        };
+        assert!(contains((4, 1), (1, 1)));
+        assert!(contains((6, 2), (1, 2)));
+        assert!(!contains((4, 1), (4, 2)));
+        assert_eq!(report.max_coverage, 8);
+        assert_eq!(
+            report
+                .coverage
+                .iter()
+                .filter(|segment| segment.elves == 8)
+                .collect::<Vec<_>>(),
+            vec![&Segment {
+                range: Range(6, 6),
+                elves: 8
+            }]
+        );
+    }
+
+    #[test]
+    fn sweep_matches_brute_force() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let assignments = assignments(&parse_data(load_file(file_path)));
+            let report = sweep(&assignments);
+            let expected = brute_force(&assignments);
+            assert_eq!(report.overlaps, expected.overlaps);
+            assert_eq!(report.containments, expected.containments);
+            assert_eq!(report.max_coverage, expected.max_coverage);
+        }
+    }
+
+    #[test]
+    fn coverage_counts_elves_per_segment() {
+        let segments = coverage([Range(1, 3), Range(4, 5), Range(3, 4), Range(8, 8)]);
+        assert_eq!(
+            segments,
+            vec![
+                Segment {
+                    range: Range(1, 2),
+                    elves: 1
+                },
+                Segment {
+                    range: Range(3, 4),
+                    elves: 2
+                },
+                Segment {
+                    range: Range(5, 5),
+                    elves: 1
+                },
+                Segment {
+                    range: Range(8, 8),
+                    elves: 1
+                },
+            ]
+        );
+    }
+}
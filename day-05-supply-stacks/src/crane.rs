@@ -0,0 +1,218 @@
+use crate::{RearrangmentProdecudure, Stack};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Batch {
+    pub how_many: usize,
+    pub reversed: bool,
+}
+
+pub trait Crane {
+    fn name(&self) -> String;
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch>;
+}
+
+pub fn rearrange<T>(
+    crane: &mut dyn Crane,
+    stacks: &mut [Stack<T>],
+    procedure: &RearrangmentProdecudure,
+) {
+    for batch in crane.batches(procedure) {
+        let mut to_insert = {
+            let from = &mut stacks[procedure.from];
+            let split_index = from.len().saturating_sub(batch.how_many);
+            from.split_off(split_index)
+        };
+        if batch.reversed {
+            to_insert.reverse();
+        }
+        stacks[procedure.to].extend(to_insert);
+    }
+}
+
+pub fn replay<T>(
+    crane: &mut dyn Crane,
+    mut stacks: Vec<Stack<T>>,
+    procedures: &[RearrangmentProdecudure],
+) -> Vec<Stack<T>> {
+    for procedure in procedures {
+        rearrange(crane, &mut stacks, procedure);
+    }
+    stacks
+}
+
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch> {
+        vec![
+            Batch {
+                how_many: 1,
+                reversed: false,
+            };
+            procedure.how_many
+        ]
+    }
+}
+
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch> {
+        vec![Batch {
+            how_many: procedure.how_many,
+            reversed: false,
+        }]
+    }
+}
+
+pub struct LimitedCrane {
+    capacity: usize,
+}
+
+impl LimitedCrane {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        format!("Limited crane ({} at once)", self.capacity)
+    }
+
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch> {
+        let mut batches = vec![
+            Batch {
+                how_many: self.capacity,
+                reversed: false,
+            };
+            procedure.how_many / self.capacity
+        ];
+        if !procedure.how_many.is_multiple_of(self.capacity) {
+            batches.push(Batch {
+                how_many: procedure.how_many % self.capacity,
+                reversed: false,
+            });
+        }
+        batches
+    }
+}
+
+#[derive(Default)]
+pub struct AlternatingCrane {
+    batches: usize,
+}
+
+impl Crane for AlternatingCrane {
+    fn name(&self) -> String {
+        "Alternating crane".to_string()
+    }
+
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch> {
+        let reversed = self.batches % 2 == 1;
+        self.batches += 1;
+        vec![Batch {
+            how_many: procedure.how_many,
+            reversed,
+        }]
+    }
+}
+
+pub struct MultiCrane {
+    arms: Vec<Box<dyn Crane>>,
+    assignment: Vec<usize>,
+}
+
+impl MultiCrane {
+    pub fn new(arms: Vec<Box<dyn Crane>>, assignment: Vec<usize>) -> Self {
+        assert!(!arms.is_empty(), "Multi crane needs at least one arm");
+        assert!(
+            assignment.iter().all(|&arm| arm < arms.len()),
+            "Stacks should be assigned to existing arms"
+        );
+        Self { arms, assignment }
+    }
+}
+
+impl Crane for MultiCrane {
+    fn name(&self) -> String {
+        let arms = self.arms.iter().map(|arm| arm.name()).collect::<Vec<_>>();
+        format!("Multi crane [{}]", arms.join(", "))
+    }
+
+    fn batches(&mut self, procedure: &RearrangmentProdecudure) -> Vec<Batch> {
+        let arm = self.assignment.get(procedure.from).copied().unwrap_or(0);
+        self.arms[arm].batches(procedure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_string_from_stacks_top, load_file, parse_data};
+
+    fn tops(crane: &mut dyn Crane, file_path: &str) -> String {
+        let (stacks, procedures) = parse_data(load_file(file_path));
+        build_string_from_stacks_top(replay(crane, stacks, &procedures))
+    }
+
+    #[test]
+    fn limited_crane_between_both_crate_movers() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            assert_eq!(
+                tops(&mut LimitedCrane::new(1), file_path),
+                tops(&mut CrateMover9000, file_path)
+            );
+            assert_eq!(
+                tops(&mut LimitedCrane::new(usize::MAX), file_path),
+                tops(&mut CrateMover9001, file_path)
+            );
+        }
+        let (stacks, _) = parse_data(load_file("./resources/test_data.txt"));
+        let stacks = replay(
+            &mut LimitedCrane::new(2),
+            stacks,
+            &[RearrangmentProdecudure::new(3, 1, 0)],
+        );
+        assert_eq!(stacks[0], vec!['Z', 'N', 'C', 'D', 'M']);
+    }
+
+    #[test]
+    fn alternating_crane_reverses_every_other_batch() {
+        let (stacks, procedures) = parse_data(load_file("./resources/test_data.txt"));
+        let stacks = replay(&mut AlternatingCrane::default(), stacks, &procedures);
+        assert_eq!(stacks, vec![vec!['M'], vec!['C'], vec!['P', 'D', 'N', 'Z']]);
+    }
+
+    #[test]
+    fn multi_crane_uses_arm_assigned_to_source_stack() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let mut crane = MultiCrane::new(vec![Box::new(CrateMover9000)], vec![]);
+            assert_eq!(
+                tops(&mut crane, file_path),
+                tops(&mut CrateMover9000, file_path)
+            );
+        }
+        let (stacks, _) = parse_data(load_file("./resources/test_data.txt"));
+        let mut crane = MultiCrane::new(
+            vec![Box::new(CrateMover9000), Box::new(CrateMover9001)],
+            vec![0, 1, 0],
+        );
+        let procedures = [
+            RearrangmentProdecudure::new(2, 1, 2),
+            RearrangmentProdecudure::new(2, 0, 2),
+        ];
+        let stacks = replay(&mut crane, stacks, &procedures);
+        assert_eq!(stacks[2], vec!['P', 'C', 'D', 'N', 'Z']);
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Display;
+
+use crate::{RearrangmentProdecudure, Stack};
+
+pub fn render_stacks<T: Display>(stacks: &[Stack<T>]) -> String {
+    render_stacks_with_ids(stacks, &(1..=stacks.len()).collect::<Vec<_>>())
+}
+
+pub fn render_stacks_with_ids<T: Display>(stacks: &[Stack<T>], ids: &[usize]) -> String {
+    let labels = stacks
+        .iter()
+        .map(|stack| stack.iter().map(T::to_string).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let label_width = labels
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(1);
+    let id_width = ids.iter().map(|id| id.to_string().len()).max().unwrap_or(1);
+    let slot_width = (label_width + 2).max(id_width);
+    let label_width = slot_width - 2;
+    let height = labels.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut rows = (0..height)
+        .rev()
+        .map(|level| {
+            labels
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("[{:^label_width$}]", label),
+                    None => " ".repeat(slot_width),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    rows.push(
+        ids.iter()
+            .map(|id| format!("{:^slot_width$}", id))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end()
+            .to_string(),
+    );
+    rows.join("\n")
+}
+
+pub fn render<T: Display>(stacks: &[Stack<T>], procedures: &[RearrangmentProdecudure]) -> String {
+    let mut drawing = render_stacks(stacks);
+    drawing.push_str("\n\n");
+    for procedure in procedures {
+        drawing.push_str(&procedure.to_string());
+        drawing.push('\n');
+    }
+    drawing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % bound as u64) as usize
+        }
+    }
+
+    #[test]
+    fn render_matches_puzzle_files() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let data = load_file(file_path);
+            let (stacks, procedures) = parse_data(data.clone());
+            assert_eq!(render(&stacks, &procedures), data);
+        }
+    }
+
+    #[test]
+    fn render_pads_multi_digit_stack_numbers() {
+        let mut stacks = vec![vec![]; 12];
+        stacks[0].push('A');
+        stacks[11].extend(['B', 'C']);
+        let expected = [
+            format!("{}[C]", " ".repeat(44)),
+            format!("[A]{}[B]", " ".repeat(41)),
+            " 1   2   3   4   5   6   7   8   9  10  11  12".to_string(),
+        ];
+        assert_eq!(render_stacks(&stacks), expected.join("\n"));
+    }
+
+    #[test]
+    fn render_and_parse_round_trip() {
+        let mut random = XorShift(2022);
+        for _ in 0..500 {
+            let stacks = (0..1 + random.next(12))
+                .map(|_| {
+                    (0..random.next(9))
+                        .map(|_| (b'A' + random.next(26) as u8) as char)
+                        .collect::<Stack<char>>()
+                })
+                .collect::<Vec<_>>();
+            let procedures = (0..random.next(4))
+                .map(|_| {
+                    RearrangmentProdecudure::new(
+                        1 + random.next(5),
+                        random.next(stacks.len()),
+                        random.next(stacks.len()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let drawing = render(&stacks, &procedures);
+            assert_eq!(parse_data(drawing), (stacks, procedures));
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+
+use crate::{
+    crane::{self, Crane},
+    RearrangmentProdecudure, Stack,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepError {
+    UnknownStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalStep {
+    pub line: usize,
+    pub procedure: RearrangmentProdecudure,
+    pub error: StepError,
+}
+
+pub struct Execution<T> {
+    crane: Box<dyn Crane>,
+    states: Vec<Vec<Stack<T>>>,
+    cursor: usize,
+}
+
+pub fn check<T>(stacks: &[Stack<T>], procedure: &RearrangmentProdecudure) -> Result<(), StepError> {
+    for stack in [procedure.from, procedure.to] {
+        if stack >= stacks.len() {
+            return Err(StepError::UnknownStack(stack + 1));
+        }
+    }
+    let available = stacks[procedure.from].len();
+    if available < procedure.how_many {
+        return Err(StepError::NotEnoughCrates {
+            stack: procedure.from + 1,
+            requested: procedure.how_many,
+            available,
+        });
+    }
+    Ok(())
+}
+
+pub fn replay<T>(
+    crane: &mut dyn Crane,
+    mut stacks: Vec<Stack<T>>,
+    lines: &[usize],
+    procedures: &[RearrangmentProdecudure],
+) -> Result<Vec<Stack<T>>, IllegalStep> {
+    for (&line, procedure) in lines.iter().zip(procedures) {
+        check(&stacks, procedure).map_err(|error| IllegalStep {
+            line,
+            procedure: *procedure,
+            error,
+        })?;
+        crane::rearrange(crane, &mut stacks, procedure);
+    }
+    Ok(stacks)
+}
+
+impl<T: Clone> Execution<T> {
+    pub fn new(crane: Box<dyn Crane>, stacks: Vec<Stack<T>>) -> Self {
+        Self {
+            crane,
+            states: vec![stacks],
+            cursor: 0,
+        }
+    }
+
+    pub fn current(&self) -> &[Stack<T>] {
+        &self.states[self.cursor]
+    }
+
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn trace(&self) -> &[Vec<Stack<T>>] {
+        &self.states[1..=self.cursor]
+    }
+
+    pub fn step(
+        &mut self,
+        line: usize,
+        procedure: &RearrangmentProdecudure,
+    ) -> Result<(), IllegalStep> {
+        check(self.current(), procedure).map_err(|error| IllegalStep {
+            line,
+            procedure: *procedure,
+            error,
+        })?;
+        let mut stacks = self.current().to_vec();
+        crane::rearrange(self.crane.as_mut(), &mut stacks, procedure);
+        self.states.truncate(self.cursor + 1);
+        self.states.push(stacks);
+        self.cursor += 1;
+        Ok(())
+    }
+
+    pub fn run(
+        &mut self,
+        lines: &[usize],
+        procedures: &[RearrangmentProdecudure],
+    ) -> Vec<IllegalStep> {
+        lines
+            .iter()
+            .zip(procedures)
+            .filter_map(|(&line, procedure)| self.step(line, procedure).err())
+            .collect()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        if self.cursor + 1 >= self.states.len() {
+            return false;
+        }
+        self.cursor += 1;
+        true
+    }
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownStack(stack) => write!(f, "stack {} does not exist", stack),
+            Self::NotEnoughCrates {
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "stack {} holds {} crates, {} requested",
+                stack, available, requested
+            ),
+        }
+    }
+}
+
+impl fmt::Display for IllegalStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.procedure, self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        build_string_from_stacks_top, crane::CrateMover9000, load_file, parse_data,
+        try_parse_numbered,
+    };
+
+    #[test]
+    fn valid_procedures_match_part_1() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let data = load_file(file_path);
+            let (stacks, procedures, lines) = try_parse_numbered(&data).unwrap();
+            let mut execution = Execution::new(Box::new(CrateMover9000), stacks);
+            assert_eq!(execution.run(&lines, &procedures), vec![]);
+            assert_eq!(execution.trace().len(), procedures.len());
+            assert_eq!(
+                build_string_from_stacks_top(execution.current().to_vec()),
+                crate::solve_part_1(file_path)
+            );
+        }
+    }
+
+    #[test]
+    fn illegal_steps_are_reported_with_line_numbers() {
+        let data = load_file("./resources/test_data.txt") + "\nmove 5 from 2 to 1\n";
+        let (stacks, mut procedures, mut lines) = try_parse_numbered(&data).unwrap();
+        assert_eq!(lines, vec![6, 7, 8, 9, 11]);
+        procedures.push(RearrangmentProdecudure::new(1, 3, 0));
+        procedures.push(RearrangmentProdecudure::new(1, 0, 6));
+        lines.extend([12, 13]);
+        let mut execution = Execution::new(Box::new(CrateMover9000), stacks.clone());
+        let errors = execution.run(&lines, &procedures);
+        assert_eq!(
+            errors.iter().map(|step| step.line).collect::<Vec<_>>(),
+            vec![11, 12, 13]
+        );
+        assert_eq!(
+            errors[0].error,
+            StepError::NotEnoughCrates {
+                stack: 2,
+                requested: 5,
+                available: 1
+            }
+        );
+        assert_eq!(errors[1].error, StepError::UnknownStack(4));
+        assert_eq!(errors[2].error, StepError::UnknownStack(7));
+        assert_eq!(
+            errors[0].to_string(),
+            "line 11: move 5 from 2 to 1: stack 2 holds 1 crates, 5 requested"
+        );
+        assert_eq!(execution.trace().len(), 4);
+        assert_eq!(
+            replay(&mut CrateMover9000, stacks, &lines, &procedures),
+            Err(errors[0])
+        );
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let (stacks, procedures) = parse_data(load_file("./resources/test_data.txt"));
+        let mut execution = Execution::new(Box::new(CrateMover9000), stacks.clone());
+        execution.run(&[6, 7, 8, 9], &procedures);
+        let finished = execution.current().to_vec();
+
+        assert!(execution.undo());
+        assert!(execution.undo());
+        assert_eq!(execution.position(), 2);
+        assert_eq!(execution.current(), execution.trace()[1].as_slice());
+        assert!(execution.redo());
+        assert!(execution.redo());
+        assert!(!execution.redo());
+        assert_eq!(execution.current(), finished.as_slice());
+
+        while execution.undo() {}
+        assert_eq!(execution.current(), stacks.as_slice());
+        execution.step(6, &procedures[0]).unwrap();
+        assert!(!execution.redo());
+        assert_eq!(execution.trace().len(), 1);
+    }
+}
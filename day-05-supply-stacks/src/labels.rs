@@ -0,0 +1,303 @@
+use std::{fmt, iter, str::FromStr};
+
+use crate::{drawing, RearrangmentProdecudure, Stack};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingStackIds,
+    InvalidStackId { line: usize, id: String },
+    RaggedRow { line: usize },
+    UnexpectedCharacter { line: usize, column: usize },
+    UnterminatedCrate { line: usize, column: usize },
+    MisalignedCrate { line: usize, column: usize },
+    DuplicateSlot { line: usize, column: usize },
+    InvalidLabel { line: usize, column: usize },
+    InvalidProcedure { line: usize },
+    UnknownStack { line: usize, id: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Strict,
+    Tolerant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout<T> {
+    pub ids: Vec<usize>,
+    pub stacks: Vec<Stack<T>>,
+}
+
+struct Token {
+    text: String,
+    first: usize,
+    last: usize,
+}
+
+fn tokens(line: &str, line_idx: usize, brackets: bool) -> Result<Vec<Token>, ParseError> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut column = 0;
+    while column < chars.len() {
+        if chars[column].is_whitespace() {
+            column += 1;
+            continue;
+        }
+        let first = column;
+        let last = if brackets {
+            if chars[first] != '[' {
+                return Err(ParseError::UnexpectedCharacter {
+                    line: line_idx + 1,
+                    column: first + 1,
+                });
+            }
+            (first..chars.len()).find(|&idx| chars[idx] == ']').ok_or(
+                ParseError::UnterminatedCrate {
+                    line: line_idx + 1,
+                    column: first + 1,
+                },
+            )?
+        } else {
+            (first..chars.len())
+                .take_while(|&idx| !chars[idx].is_whitespace())
+                .last()
+                .unwrap()
+        };
+        let text = if brackets {
+            chars[first + 1..last].iter().collect::<String>()
+        } else {
+            chars[first..=last].iter().collect::<String>()
+        };
+        tokens.push(Token {
+            text: text.trim().to_string(),
+            first,
+            last,
+        });
+        column = last + 1;
+    }
+    Ok(tokens)
+}
+
+fn parse_stacks<T: FromStr>(drawing: &[&str], mode: Mode) -> Result<Layout<T>, ParseError> {
+    let (numbers, rows) = drawing.split_last().ok_or(ParseError::MissingStackIds)?;
+    let numbers_line = drawing.len() - 1;
+    let columns = tokens(numbers, numbers_line, false)?;
+    let mut ids = Vec::new();
+    for column in &columns {
+        match column.text.parse::<usize>() {
+            Ok(id) if !ids.contains(&id) => ids.push(id),
+            _ => {
+                return Err(ParseError::InvalidStackId {
+                    line: numbers_line + 1,
+                    id: column.text.clone(),
+                })
+            }
+        }
+    }
+    if ids.is_empty() {
+        return Err(ParseError::MissingStackIds);
+    }
+
+    let width = drawing
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap();
+    if mode == Mode::Strict {
+        if let Some(line_idx) = rows.iter().position(|row| row.chars().count() != width) {
+            return Err(ParseError::RaggedRow { line: line_idx + 1 });
+        }
+    }
+    let mut stacks = iter::repeat_with(Stack::new)
+        .take(ids.len())
+        .collect::<Vec<_>>();
+    for (line_idx, row) in rows.iter().enumerate().rev() {
+        let mut filled = vec![false; ids.len()];
+        for token in tokens(row, line_idx, true)? {
+            let (line, column) = (line_idx + 1, token.first + 1);
+            let center = token.first + token.last;
+            let stack = (0..columns.len())
+                .min_by_key(|&idx| (columns[idx].first + columns[idx].last).abs_diff(center))
+                .unwrap();
+            let aligned = columns[stack].first <= token.last && token.first <= columns[stack].last;
+            if mode == Mode::Strict && !aligned {
+                return Err(ParseError::MisalignedCrate { line, column });
+            }
+            if filled[stack] {
+                return Err(ParseError::DuplicateSlot { line, column });
+            }
+            filled[stack] = true;
+            let label = token
+                .text
+                .parse()
+                .map_err(|_| ParseError::InvalidLabel { line, column })?;
+            stacks[stack].push(label);
+        }
+    }
+    Ok(Layout { ids, stacks })
+}
+
+impl<T> Layout<T> {
+    fn index(&self, id: &str, line: usize) -> Result<usize, ParseError> {
+        let id = id
+            .parse::<usize>()
+            .map_err(|_| ParseError::InvalidProcedure { line })?;
+        self.ids
+            .iter()
+            .position(|&known| known == id)
+            .ok_or(ParseError::UnknownStack { line, id })
+    }
+}
+
+type Parsed<T> = (Layout<T>, Vec<RearrangmentProdecudure>, Vec<usize>);
+
+pub fn parse<T: FromStr>(data: &str, mode: Mode) -> Result<Parsed<T>, ParseError> {
+    let lines = data.lines().collect::<Vec<_>>();
+    let blank = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let layout = parse_stacks(&lines[..blank], mode)?;
+
+    let mut procedures = Vec::new();
+    let mut procedure_lines = Vec::new();
+    for (line_idx, line) in lines.iter().enumerate().skip(blank + 1) {
+        let line_number = line_idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let ["move", how_many, "from", from, "to", to] = fields.as_slice() else {
+            return Err(ParseError::InvalidProcedure { line: line_number });
+        };
+        procedures.push(RearrangmentProdecudure::new(
+            how_many
+                .parse()
+                .map_err(|_| ParseError::InvalidProcedure { line: line_number })?,
+            layout.index(from, line_number)?,
+            layout.index(to, line_number)?,
+        ));
+        procedure_lines.push(line_number);
+    }
+    Ok((layout, procedures, procedure_lines))
+}
+
+impl<T: fmt::Display> fmt::Display for Layout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            drawing::render_stacks_with_ids(&self.stacks, &self.ids)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        build_string_from_stacks_top,
+        crane::{self, CrateMover9000, CrateMover9001},
+        load_file, parse_data,
+    };
+
+    fn labels(stack: &[&str]) -> Stack<String> {
+        stack.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn multi_character_labels_and_sparse_ids() {
+        let data = load_file("./resources/test_data_labels.txt");
+        let (layout, procedures, lines) = parse::<String>(&data, Mode::Tolerant).unwrap();
+        assert_eq!(lines, vec![6, 7]);
+        assert_eq!(layout.ids, vec![2, 4, 7, 11]);
+        assert_eq!(
+            layout.stacks,
+            vec![
+                labels(&["Z9", "X"]),
+                labels(&["M", "C", "AB1"]),
+                labels(&["P"]),
+                labels(&["R", "Q7"]),
+            ]
+        );
+        assert_eq!(
+            procedures,
+            vec![
+                RearrangmentProdecudure::new(1, 1, 0),
+                RearrangmentProdecudure::new(2, 0, 3),
+            ]
+        );
+
+        let tops = |crane: &mut dyn crane::Crane| {
+            build_string_from_stacks_top(crane::replay(crane, layout.stacks.clone(), &procedures))
+        };
+        assert_eq!(tops(&mut CrateMover9000), "Z9CPX");
+        assert_eq!(tops(&mut CrateMover9001), "Z9CPAB1");
+    }
+
+    #[test]
+    fn string_labels_match_char_labels() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let data = load_file(file_path);
+            let (layout, procedures, _) = parse::<String>(&data, Mode::Tolerant).unwrap();
+            let (stacks, expected_procedures) = parse_data(data);
+            let expected_stacks = stacks
+                .iter()
+                .map(|stack| stack.iter().map(char::to_string).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            assert_eq!(layout.stacks, expected_stacks);
+            assert_eq!(layout.ids, (1..=stacks.len()).collect::<Vec<_>>());
+            assert_eq!(procedures, expected_procedures);
+        }
+    }
+
+    #[test]
+    fn strict_mode_requires_padded_aligned_rows() {
+        let data = load_file("./resources/test_data_labels.txt");
+        assert_eq!(
+            parse::<String>(&data, Mode::Strict),
+            Err(ParseError::RaggedRow { line: 1 })
+        );
+        let (layout, _, _) = parse::<String>(&data, Mode::Tolerant).unwrap();
+        let rendered = layout.to_string();
+        let width = rendered.lines().map(str::len).max().unwrap();
+        let padded = rendered
+            .lines()
+            .map(|line| format!("{:<width$}\n", line))
+            .collect::<String>();
+        assert_eq!(parse(&padded, Mode::Strict).unwrap().0, layout);
+        assert_eq!(parse(&rendered, Mode::Tolerant).unwrap().0, layout);
+
+        assert_eq!(
+            parse::<char>("  [A] \n 1   2\n", Mode::Strict),
+            Err(ParseError::MisalignedCrate { line: 1, column: 3 })
+        );
+        assert_eq!(
+            parse::<char>("[A]\n 1 \n\nmove 1 from 1 to 3\n", Mode::Strict),
+            Err(ParseError::UnknownStack { line: 4, id: 3 })
+        );
+        assert_eq!(
+            parse::<char>("[A\n 1\n", Mode::Tolerant),
+            Err(ParseError::UnterminatedCrate { line: 1, column: 1 })
+        );
+    }
+
+    #[test]
+    fn duplicate_slots_and_invalid_labels_are_reported() {
+        assert_eq!(
+            parse::<char>("[A][B]\n 1\n", Mode::Tolerant),
+            Err(ParseError::DuplicateSlot { line: 1, column: 4 })
+        );
+        assert_eq!(
+            parse::<char>("[AB]\n 1\n", Mode::Tolerant),
+            Err(ParseError::InvalidLabel { line: 1, column: 1 })
+        );
+        assert_eq!(
+            parse::<String>("[AB]\n 1\n", Mode::Tolerant)
+                .unwrap()
+                .0
+                .stacks,
+            vec![labels(&["AB"])]
+        );
+    }
+}
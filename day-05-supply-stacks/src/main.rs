@@ -1,55 +1,38 @@
-use std::fs;
+use std::{
+    env, fmt, fs,
+    io::{self, BufRead},
+};
+
+use executor::Execution;
+
+use crane::{AlternatingCrane, Crane, CrateMover9000, CrateMover9001, LimitedCrane, MultiCrane};
+
+mod crane;
+mod drawing;
+mod executor;
+mod labels;
+mod planner;
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
 
-fn parse_data(string_data: String) -> (Vec<Stack<char>>, Vec<RearrangmentProdecudure>) {
-    let mut lines = string_data.lines();
-    let stacks = lines
-        .by_ref()
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            line.replace("    ", "[_] ")
-                .replace("[", " ")
-                .replace("]", " ")
-                .replace(" ", "")
-        })
-        .fold(vec![], |mut stacks, line| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| c != &'_' && !c.is_numeric())
-                .for_each(|(idx, c)| {
-                    while stacks.len() <= idx {
-                        stacks.push(Stack::new());
-                    }
-                    stacks[idx].push(c);
-                });
-            stacks
-        })
-        .into_iter()
-        .map(|mut stack| {
-            stack.reverse();
-            stack
-        })
-        .collect();
-
-    let rearrangment_procedures = lines
-        .map(|line| {
-            let procedure_data = line
-                .replace("move ", "")
-                .replace("from", "")
-                .replace(" to", "");
-            let mut procedure_data = procedure_data.split_whitespace();
-            RearrangmentProdecudure::new(
-                procedure_data.next().unwrap().parse::<usize>().unwrap(),
-                procedure_data.next().unwrap().parse::<usize>().unwrap() - 1,
-                procedure_data.next().unwrap().parse::<usize>().unwrap() - 1,
-            )
-        })
-        .collect();
+type ParsedData = (Vec<Stack<char>>, Vec<RearrangmentProdecudure>);
+type NumberedData = (Vec<Stack<char>>, Vec<RearrangmentProdecudure>, Vec<usize>);
+
+fn try_parse_numbered(string_data: &str) -> Result<NumberedData, labels::ParseError> {
+    labels::parse(string_data, labels::Mode::Tolerant).map(
+        |(layout, rearrangment_procedures, lines)| (layout.stacks, rearrangment_procedures, lines),
+    )
+}
 
-    (stacks, rearrangment_procedures)
+fn try_parse_data(string_data: &str) -> Result<ParsedData, labels::ParseError> {
+    try_parse_numbered(string_data)
+        .map(|(stacks, rearrangment_procedures, _)| (stacks, rearrangment_procedures))
+}
+
+fn parse_data(string_data: String) -> ParsedData {
+    try_parse_data(&string_data).unwrap_or_else(|error| panic!("Invalid drawing: {:?}", error))
 }
 
 type Stack<T> = Vec<T>;
@@ -67,43 +50,44 @@ impl RearrangmentProdecudure {
     }
 }
 
-fn build_string_from_stacks_top(stacks: Vec<Stack<char>>) -> String {
+impl fmt::Display for RearrangmentProdecudure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.how_many,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+fn build_string_from_stacks_top<T: fmt::Display>(stacks: Vec<Stack<T>>) -> String {
     stacks
         .into_iter()
         .fold("".to_string(), |mut acc, mut stack| {
             if let Some(value) = stack.pop() {
-                acc.push(value)
+                acc.push_str(&value.to_string())
             }
             acc
         })
 }
 
-pub fn solve_part_1(file_path: &str) -> String {
+fn solve(file_path: &str, crane: &mut dyn Crane) -> String {
     let data = load_file(file_path);
-    let (mut stacks, rearrangment_procedures) = parse_data(data);
-    rearrangment_procedures.into_iter().for_each(|procedure| {
-        for _ in 0..procedure.how_many {
-            if let Some(value) = stacks[procedure.from].pop() {
-                stacks[procedure.to].push(value);
-            }
-        }
-    });
+    let (stacks, rearrangment_procedures, lines) =
+        try_parse_numbered(&data).unwrap_or_else(|error| panic!("Invalid drawing: {:?}", error));
+    let stacks = executor::replay(crane, stacks, &lines, &rearrangment_procedures)
+        .unwrap_or_else(|error| panic!("Illegal procedure at {}", error));
     build_string_from_stacks_top(stacks)
 }
 
+pub fn solve_part_1(file_path: &str) -> String {
+    solve(file_path, &mut CrateMover9000)
+}
+
 pub fn solve_part_2(file_path: &str) -> String {
-    let data = load_file(file_path);
-    let (mut stacks, rearrangment_procedures) = parse_data(data);
-    rearrangment_procedures.into_iter().for_each(|procedure| {
-        let to_insert = {
-            let from = &mut stacks[procedure.from];
-            let split_index = from.len().checked_sub(procedure.how_many).unwrap_or(0);
-            from.split_off(split_index)
-        };
-        let to = &mut stacks[procedure.to];
-        to.extend(to_insert);
-    });
-    build_string_from_stacks_top(stacks)
+    solve(file_path, &mut CrateMover9001)
 }
 
 fn part_1(file_path: &str) {
@@ -116,10 +100,191 @@ fn part_2(file_path: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn cranes(file_path: &str) {
+    let data = load_file(file_path);
+    let (stacks, rearrangment_procedures) = parse_data(data);
+    let mut cranes: Vec<Box<dyn Crane>> = vec![
+        Box::new(CrateMover9000),
+        Box::new(CrateMover9001),
+        Box::new(LimitedCrane::new(3)),
+        Box::new(AlternatingCrane::default()),
+        Box::new(MultiCrane::new(
+            vec![Box::new(CrateMover9000), Box::new(CrateMover9001)],
+            (0..stacks.len()).map(|idx| idx % 2).collect(),
+        )),
+    ];
+    for crane in cranes.iter_mut() {
+        let result = crane::replay(crane.as_mut(), stacks.clone(), &rearrangment_procedures);
+        println!(
+            "{}: {:?}",
+            crane.name(),
+            build_string_from_stacks_top(result)
+        );
+    }
+}
+
+fn draw(file_path: &str, procedures: Option<usize>) {
+    let data = load_file(file_path);
+    let (stacks, rearrangment_procedures) = parse_data(data);
+    let procedures = procedures
+        .unwrap_or(rearrangment_procedures.len())
+        .min(rearrangment_procedures.len());
+    let (done, remaining) = rearrangment_procedures.split_at(procedures);
+    let stacks = crane::replay(&mut CrateMover9000, stacks, done);
+    print!("{}", drawing::render(&stacks, remaining));
+}
+
+fn validate(file_path: &str) {
+    let data = load_file(file_path);
+    let (stacks, rearrangment_procedures, lines) = match try_parse_numbered(&data) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            println!("Invalid drawing: {:?}", error);
+            return;
+        }
+    };
+    let mut execution = Execution::new(Box::new(CrateMover9000), stacks);
+    let errors = execution.run(&lines, &rearrangment_procedures);
+    println!(
+        "Applied {} of {} procedures",
+        execution.trace().len(),
+        rearrangment_procedures.len()
+    );
+    for error in errors {
+        println!("{}", error);
+    }
+    println!(
+        "Result: {:?}",
+        build_string_from_stacks_top(execution.current().to_vec())
+    );
+}
+
+fn step(file_path: &str) {
+    let data = load_file(file_path);
+    let (stacks, rearrangment_procedures, lines) = match try_parse_numbered(&data) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            println!("Invalid drawing: {:?}", error);
+            return;
+        }
+    };
+    let mut execution = Execution::new(Box::new(CrateMover9000), stacks);
+    let mut next_procedure = vec![0];
+    println!("{}", drawing::render_stacks(execution.current()));
+    for command in io::stdin().lock().lines() {
+        let position = execution.position();
+        let procedure_idx = next_procedure[position];
+        match command
+            .expect("Should have been able to read a command")
+            .trim()
+        {
+            "next" | "n" => match rearrangment_procedures.get(procedure_idx) {
+                Some(procedure) => match execution.step(lines[procedure_idx], procedure) {
+                    Ok(()) => {
+                        next_procedure.truncate(position + 1);
+                        next_procedure.push(procedure_idx + 1);
+                        println!("{}", procedure);
+                    }
+                    Err(error) => {
+                        next_procedure[position] += 1;
+                        println!("Skipped {}", error);
+                    }
+                },
+                None => println!("No procedures left"),
+            },
+            "undo" | "u" if !execution.undo() => println!("Nothing to undo"),
+            "redo" | "r" if !execution.redo() => println!("Nothing to redo"),
+            "undo" | "u" | "redo" | "r" => {}
+            "quit" | "q" => break,
+            command => println!(
+                "Unknown command {:?}, use next, undo, redo or quit",
+                command
+            ),
+        }
+        println!("{}", drawing::render_stacks(execution.current()));
+    }
+}
+
+fn plan(file_path: &str, target_path: &str, model: &str) {
+    let (stacks, _) = parse_data(load_file(file_path));
+    let (target, _) = parse_data(load_file(target_path));
+    let mut crane: Box<dyn Crane> = match model {
+        "9000" => Box::new(CrateMover9000),
+        "9001" => Box::new(CrateMover9001),
+        _ => panic!("Crane model should be 9000 or 9001"),
+    };
+    match planner::plan(crane.as_mut(), &stacks, &target) {
+        Ok(procedures) => procedures
+            .iter()
+            .for_each(|procedure| println!("{}", procedure)),
+        Err(error) => println!("No plan found: {:?}", error),
+    }
+}
+
+fn with_labels(file_path: &str, mode: labels::Mode) {
+    let data = load_file(file_path);
+    let (layout, rearrangment_procedures, _) = match labels::parse::<String>(&data, mode) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            println!("Invalid drawing: {:?}", error);
+            return;
+        }
+    };
+    println!("{}", layout);
+    for (part, mut crane) in [
+        (1, Box::new(CrateMover9000) as Box<dyn Crane>),
+        (2, Box::new(CrateMover9001)),
+    ] {
+        let stacks = crane::replay(
+            crane.as_mut(),
+            layout.stacks.clone(),
+            &rearrangment_procedures,
+        );
+        println!(
+            "Part {} result: {:?}",
+            part,
+            build_string_from_stacks_top(stacks)
+        );
+    }
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    match env::args().nth(1).as_deref() {
+        Some("draw") => draw(
+            FILE_PATH,
+            env::args().nth(2).map(|procedures| {
+                procedures
+                    .parse()
+                    .expect("Should be a number of procedures")
+            }),
+        ),
+        Some("validate") => validate(&env::args().nth(2).unwrap_or(FILE_PATH.to_string())),
+        Some("step") => step(&env::args().nth(2).unwrap_or(FILE_PATH.to_string())),
+        Some("plan") => plan(
+            &env::args()
+                .nth(2)
+                .unwrap_or("./resources/test_data.txt".to_string()),
+            &env::args()
+                .nth(3)
+                .unwrap_or("./resources/test_target.txt".to_string()),
+            &env::args().nth(4).unwrap_or("9000".to_string()),
+        ),
+        Some("labels") => with_labels(
+            &env::args()
+                .nth(2)
+                .unwrap_or("./resources/test_data_labels.txt".to_string()),
+            match env::args().nth(3).as_deref() {
+                Some("strict") => labels::Mode::Strict,
+                _ => labels::Mode::Tolerant,
+            },
+        ),
+        Some("cranes") => cranes(&env::args().nth(2).unwrap_or(FILE_PATH.to_string())),
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -144,6 +309,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn procedures_on_unknown_stacks_are_rejected() {
+        let drawing = load_file("./resources/test_data.txt");
+        for (procedure, id) in [("move 1 from 0 to 1", 0), ("move 1 from 1 to 4", 4)] {
+            assert_eq!(
+                try_parse_data(&format!("{}{}\n", drawing, procedure)),
+                Err(labels::ParseError::UnknownStack { line: 10, id })
+            );
+        }
+    }
+
     #[test]
     fn test_part_1() {
         let result = solve_part_1("./resources/test_data.txt");
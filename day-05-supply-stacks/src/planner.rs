@@ -0,0 +1,153 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use crate::{
+    crane::{self, Crane},
+    RearrangmentProdecudure, Stack,
+};
+
+const MAX_STATES: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanError {
+    Unreachable,
+    TooLarge,
+}
+
+fn sorted_crates<T: Clone + Ord>(stacks: &[Stack<T>]) -> Vec<T> {
+    let mut crates = stacks.iter().flatten().cloned().collect::<Vec<_>>();
+    crates.sort();
+    crates
+}
+
+fn lower_bound<T: PartialEq>(stacks: &[Stack<T>], target: &[Stack<T>]) -> usize {
+    let (mut to_empty, mut to_fill) = (0, 0);
+    for (stack, wanted) in stacks.iter().zip(target) {
+        let common = stack
+            .iter()
+            .zip(wanted)
+            .take_while(|(current, wanted)| current == wanted)
+            .count();
+        if common < stack.len() {
+            to_empty += 1;
+        }
+        if common < wanted.len() {
+            to_fill += 1;
+        }
+    }
+    to_empty.max(to_fill)
+}
+
+pub fn plan<T: Clone + Ord + Hash>(
+    crane: &mut dyn Crane,
+    stacks: &[Stack<T>],
+    target: &[Stack<T>],
+) -> Result<Vec<RearrangmentProdecudure>, PlanError> {
+    if stacks.len() != target.len() || sorted_crates(stacks) != sorted_crates(target) {
+        return Err(PlanError::Unreachable);
+    }
+
+    let mut states = vec![stacks.to_vec()];
+    let mut seen = HashMap::from([(stacks.to_vec(), 0)]);
+    let mut parents: Vec<Option<(usize, RearrangmentProdecudure)>> = vec![None];
+    let mut moves = vec![0];
+    let mut queue = BinaryHeap::from([(Reverse(lower_bound(stacks, target)), 0)]);
+
+    while let Some((_, state)) = queue.pop() {
+        if states[state] == target {
+            let mut procedures = Vec::new();
+            let mut current = state;
+            while let Some((parent, procedure)) = parents[current] {
+                procedures.push(procedure);
+                current = parent;
+            }
+            procedures.reverse();
+            return Ok(procedures);
+        }
+        for from in 0..stacks.len() {
+            for to in (0..stacks.len()).filter(|&to| to != from) {
+                for how_many in 1..=states[state][from].len() {
+                    let procedure = RearrangmentProdecudure::new(how_many, from, to);
+                    let mut next = states[state].clone();
+                    crane::rearrange(crane, &mut next, &procedure);
+                    let next_moves = moves[state] + 1;
+                    if let Some(&known) = seen.get(&next) {
+                        if moves[known] <= next_moves {
+                            continue;
+                        }
+                    }
+                    if states.len() >= MAX_STATES {
+                        return Err(PlanError::TooLarge);
+                    }
+                    let estimate = next_moves + lower_bound(&next, target);
+                    seen.insert(next.clone(), states.len());
+                    queue.push((Reverse(estimate), states.len()));
+                    states.push(next);
+                    parents.push(Some((state, procedure)));
+                    moves.push(next_moves);
+                }
+            }
+        }
+    }
+    Err(PlanError::Unreachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001},
+        load_file, parse_data,
+    };
+
+    #[test]
+    fn crate_movers_need_different_plans() {
+        let stacks = vec![vec!['A', 'B'], vec![], vec![]];
+        let target = vec![vec![], vec![], vec!['A', 'B']];
+        assert_eq!(
+            plan(&mut CrateMover9001, &stacks, &target),
+            Ok(vec![RearrangmentProdecudure::new(2, 0, 2)])
+        );
+        assert_eq!(
+            plan(&mut CrateMover9000, &stacks, &target).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn plan_reaches_target_in_fewest_moves() {
+        let (stacks, _) = parse_data(load_file("./resources/test_data.txt"));
+        let (target, _) = parse_data(load_file("./resources/test_target.txt"));
+        for crane in [
+            &mut CrateMover9000 as &mut dyn Crane,
+            &mut CrateMover9001 as &mut dyn Crane,
+        ] {
+            let procedures = plan(crane, &stacks, &target).unwrap();
+            assert_eq!(crane::replay(crane, stacks.clone(), &procedures), target);
+        }
+        assert_eq!(
+            plan(&mut CrateMover9000, &stacks, &target).unwrap().len(),
+            3
+        );
+        assert_eq!(
+            plan(&mut CrateMover9001, &stacks, &target).unwrap().len(),
+            4
+        );
+    }
+
+    #[test]
+    fn different_crates_are_unreachable() {
+        let stacks = vec![vec!['A'], vec!['B']];
+        assert_eq!(
+            plan(&mut CrateMover9000, &stacks, &[vec!['A'], vec!['C']]),
+            Err(PlanError::Unreachable)
+        );
+        assert_eq!(
+            plan(&mut CrateMover9000, &stacks, &[vec!['A', 'B']]),
+            Err(PlanError::Unreachable)
+        );
+    }
+}
@@ -1,15 +1,22 @@
-use std::{collections::HashSet, fs};
+use std::{collections::HashSet, env, fs, fs::File};
+
+use stream::MarkerDetector;
+
+mod segments;
+mod stream;
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
 
-pub fn find_distinct_characters_idx(data: String, window_width: usize) -> Option<usize> {
-    data.chars()
-        .collect::<Vec<_>>()
+fn distinct_characters_idxs(
+    chars: &[char],
+    window_width: usize,
+) -> impl Iterator<Item = usize> + '_ {
+    chars
         .windows(window_width)
         .enumerate()
-        .find_map(|(idx, window)| {
+        .filter_map(move |(idx, window)| {
             if HashSet::<char>::from_iter(window.to_owned()).len() == window_width {
                 Some(idx + window_width)
             } else {
@@ -18,6 +25,10 @@ pub fn find_distinct_characters_idx(data: String, window_width: usize) -> Option
         })
 }
 
+pub fn find_distinct_characters_idx(data: String, window_width: usize) -> Option<usize> {
+    distinct_characters_idxs(&data.chars().collect::<Vec<_>>(), window_width).next()
+}
+
 pub fn solve_part_1(data: String) -> Option<usize> {
     find_distinct_characters_idx(data, 4)
 }
@@ -36,11 +47,56 @@ fn part_2(data: String) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn stream(file_path: &str, window_width: usize) {
+    let file = File::open(file_path).expect("Should have been able to open the file");
+    let Some(mut detector) = MarkerDetector::new(file, window_width) else {
+        println!("Width should be positive");
+        return;
+    };
+    let mut markers = 0;
+    for marker in detector.by_ref() {
+        let marker = marker.expect("Should have been able to read the file");
+        if markers == 0 {
+            println!("First marker after {} characters", marker);
+        }
+        markers += 1;
+    }
+    println!(
+        "Markers of width {} in {} characters: {}",
+        window_width,
+        detector.offset(),
+        markers
+    );
+}
+
+fn segments(data: String, window_width: usize) {
+    match segments::MarkerReport::new(data.trim(), window_width) {
+        Some(report) => print!("{}", report),
+        None => println!("Width should be positive"),
+    }
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    let data = load_file(FILE_PATH);
-    part_1(data.clone());
-    part_2(data.clone());
+    match env::args().nth(1).as_deref() {
+        Some("stream") => stream(
+            &env::args().nth(3).unwrap_or(FILE_PATH.to_string()),
+            env::args()
+                .nth(2)
+                .map_or(4, |width| width.parse().expect("Width should be a number")),
+        ),
+        Some("segments") => segments(
+            load_file(FILE_PATH),
+            env::args()
+                .nth(2)
+                .map_or(4, |width| width.parse().expect("Width should be a number")),
+        ),
+        _ => {
+            let data = load_file(FILE_PATH);
+            part_1(data.clone());
+            part_2(data.clone());
+        }
+    }
 }
 
 #[cfg(test)]
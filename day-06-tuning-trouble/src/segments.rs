@@ -0,0 +1,163 @@
+use std::fmt;
+
+use crate::distinct_characters_idxs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Segment {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkerReport {
+    pub window_width: usize,
+    pub length: usize,
+    pub markers: Vec<usize>,
+    pub segments: Vec<Segment>,
+    pub remainder: Segment,
+    pub longest_without_marker: Segment,
+}
+
+impl MarkerReport {
+    pub fn new(data: &str, window_width: usize) -> Option<Self> {
+        if window_width == 0 {
+            return None;
+        }
+        let chars = data.chars().collect::<Vec<_>>();
+        let markers = distinct_characters_idxs(&chars, window_width).collect::<Vec<_>>();
+
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for &marker in &markers {
+            if marker - window_width >= start {
+                segments.push(Segment { start, end: marker });
+                start = marker;
+            }
+        }
+        let remainder = Segment {
+            start,
+            end: chars.len(),
+        };
+
+        let longest_without_marker = [0]
+            .into_iter()
+            .chain(markers.iter().copied())
+            .chain([chars.len()])
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|pair| Segment {
+                start: pair[0],
+                end: pair[1],
+            })
+            .rev()
+            .max_by_key(Segment::len)
+            .unwrap();
+
+        Some(Self {
+            window_width,
+            length: chars.len(),
+            markers,
+            segments,
+            remainder,
+            longest_without_marker,
+        })
+    }
+
+    pub fn segment_lengths(&self) -> Vec<usize> {
+        self.segments.iter().map(Segment::len).collect()
+    }
+}
+
+impl fmt::Display for MarkerReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lengths = self.segment_lengths();
+        writeln!(
+            f,
+            "Markers of width {} in {} characters: {}",
+            self.window_width,
+            self.length,
+            self.markers.len()
+        )?;
+        writeln!(f, "Segments: {}", self.segments.len())?;
+        if let (Some(min), Some(max)) = (lengths.iter().min(), lengths.iter().max()) {
+            writeln!(
+                f,
+                "Segment length: min {}, max {}, mean {:.2}",
+                min,
+                max,
+                lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+            )?;
+        }
+        writeln!(
+            f,
+            "Unterminated remainder: {} characters",
+            self.remainder.len()
+        )?;
+        writeln!(
+            f,
+            "Longest run without marker: {} characters ({}..{})",
+            self.longest_without_marker.len(),
+            self.longest_without_marker.start,
+            self.longest_without_marker.end
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_distinct_characters_idx, load_file};
+
+    #[test]
+    fn segments_split_at_non_overlapping_markers() {
+        let report = MarkerReport::new("abcdabcd", 4).unwrap();
+        assert_eq!(report.markers, vec![4, 5, 6, 7, 8]);
+        assert_eq!(
+            report.segments,
+            vec![Segment { start: 0, end: 4 }, Segment { start: 4, end: 8 }]
+        );
+        assert_eq!(report.remainder.len(), 0);
+
+        let report = MarkerReport::new("aaaabcdaaaa", 4).unwrap();
+        assert_eq!(report.markers, vec![7, 8]);
+        assert_eq!(report.segment_lengths(), vec![7]);
+        assert_eq!(report.remainder, Segment { start: 7, end: 11 });
+        assert_eq!(report.longest_without_marker, Segment { start: 0, end: 7 });
+    }
+
+    #[test]
+    fn first_marker_matches_part_solutions() {
+        let data = load_file("./resources/puzzle.txt");
+        for window_width in [4, 14] {
+            let report = MarkerReport::new(data.trim(), window_width).unwrap();
+            assert_eq!(
+                report.markers.first().copied(),
+                find_distinct_characters_idx(data.clone(), window_width)
+            );
+            assert_eq!(
+                report.segments.iter().map(Segment::len).sum::<usize>() + report.remainder.len(),
+                report.length
+            );
+            assert!(report
+                .segment_lengths()
+                .iter()
+                .all(|&len| len >= window_width));
+        }
+    }
+
+    #[test]
+    fn no_marker_leaves_whole_stream_unsegmented() {
+        let report = MarkerReport::new("aabbaabb", 3).unwrap();
+        assert_eq!(report.markers, vec![]);
+        assert_eq!(report.segments, vec![]);
+        assert_eq!(report.remainder, Segment { start: 0, end: 8 });
+        assert_eq!(report.longest_without_marker, report.remainder);
+        assert_eq!(MarkerReport::new("aabbaabb", 0), None);
+    }
+}
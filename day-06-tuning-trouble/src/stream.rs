@@ -0,0 +1,153 @@
+use std::io::{self, ErrorKind, Read};
+
+const BUFFER_SIZE: usize = 1 << 16;
+
+pub struct MarkerDetector<R> {
+    reader: R,
+    width: usize,
+    counts: [usize; 256],
+    distinct: usize,
+    window: Vec<u8>,
+    buffer: Box<[u8]>,
+    filled: usize,
+    position: usize,
+    offset: usize,
+}
+
+impl<R: Read> MarkerDetector<R> {
+    pub fn new(reader: R, width: usize) -> Option<Self> {
+        if width == 0 {
+            return None;
+        }
+        Some(Self {
+            reader,
+            width,
+            counts: [0; 256],
+            distinct: 0,
+            window: vec![0; width],
+            buffer: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            filled: 0,
+            position: 0,
+            offset: 0,
+        })
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn next_byte(&mut self) -> io::Result<Option<u8>> {
+        while self.position == self.filled {
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return Ok(None),
+                Ok(filled) => {
+                    self.filled = filled;
+                    self.position = 0;
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        self.position += 1;
+        Ok(Some(self.buffer[self.position - 1]))
+    }
+
+    fn push(&mut self, byte: u8) -> bool {
+        let slot = self.offset % self.width;
+        if self.offset >= self.width {
+            let leaving = self.window[slot] as usize;
+            self.counts[leaving] -= 1;
+            if self.counts[leaving] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        self.window[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 1 {
+            self.distinct += 1;
+        }
+        self.offset += 1;
+        self.distinct == self.width
+    }
+}
+
+impl<R: Read> Iterator for MarkerDetector<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_byte() {
+                Ok(Some(byte)) => {
+                    if self.push(byte) {
+                        return Some(Ok(self.offset));
+                    }
+                }
+                Ok(None) => return None,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_distinct_characters_idx, load_file};
+
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buffer[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn first_marker_matches_window_search() {
+        let data = load_file("./resources/puzzle.txt");
+        for width in [1, 2, 4, 7, 14, 20] {
+            let mut detector = MarkerDetector::new(data.as_bytes(), width).unwrap();
+            assert_eq!(
+                detector.next().transpose().unwrap(),
+                find_distinct_characters_idx(data.clone(), width)
+            );
+        }
+    }
+
+    #[test]
+    fn markers_are_reported_incrementally() {
+        let data = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let markers = MarkerDetector::new(Trickle(data), 4)
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        let expected = (4..=data.len())
+            .filter(|&end| {
+                find_distinct_characters_idx(
+                    String::from_utf8(data[end - 4..end].to_vec()).unwrap(),
+                    4,
+                )
+                .is_some()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(markers[0], 7);
+        assert_eq!(markers, expected);
+
+        let mut detector = MarkerDetector::new(&data[..], 14).unwrap();
+        assert_eq!(detector.next().transpose().unwrap(), Some(19));
+        assert_eq!(detector.offset(), 19);
+    }
+
+    #[test]
+    fn too_wide_windows_never_match() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(MarkerDetector::new(&data[..], 256).unwrap().count(), 1);
+        assert_eq!(MarkerDetector::new(&data[..], 257).unwrap().count(), 0);
+        assert!(MarkerDetector::new(&data[..], 0).is_none());
+    }
+}
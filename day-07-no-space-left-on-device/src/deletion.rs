@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::tree::{FileSystem, NodeId, ROOT};
+
+const MAX_TOTAL: usize = 1 << 28;
+const MAX_STATES: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanError {
+    Unreachable,
+    TooLarge,
+}
+
+fn fully_selected(file_system: &FileSystem, id: NodeId, selected: &HashSet<NodeId>) -> bool {
+    if !file_system.is_dir(id) {
+        return selected.contains(&id);
+    }
+    let children = file_system.children(id);
+    file_system.size(id) > 0
+        && children.iter().all(|&child| {
+            file_system.size(child) == 0 || fully_selected(file_system, child, selected)
+        })
+}
+
+fn collapse(
+    file_system: &FileSystem,
+    id: NodeId,
+    selected: &HashSet<NodeId>,
+    plan: &mut Vec<NodeId>,
+) {
+    if fully_selected(file_system, id, selected) {
+        plan.push(id);
+        return;
+    }
+    for child in file_system.children(id) {
+        collapse(file_system, child, selected, plan);
+    }
+}
+
+pub fn plan(file_system: &FileSystem, to_free: usize) -> Result<Vec<NodeId>, PlanError> {
+    if to_free == 0 {
+        return Ok(vec![]);
+    }
+    let upper_bound = file_system
+        .walk()
+        .into_iter()
+        .map(|id| file_system.size(id))
+        .filter(|&size| size >= to_free)
+        .min()
+        .ok_or(PlanError::Unreachable)?;
+    if upper_bound > MAX_TOTAL {
+        return Err(PlanError::TooLarge);
+    }
+    let files = file_system
+        .files()
+        .into_iter()
+        .filter(|&id| (1..=upper_bound).contains(&file_system.size(id)))
+        .collect::<Vec<_>>();
+
+    let words = upper_bound / 64 + 1;
+    let mut reachable = vec![0u64; words];
+    reachable[0] = 1;
+    let mut reached_by = HashMap::new();
+    for (idx, &file) in files.iter().enumerate() {
+        let size = file_system.size(file);
+        let (shift_words, shift_bits) = (size / 64, size % 64);
+        for word in (shift_words..words).rev() {
+            let mut shifted = reachable[word - shift_words] << shift_bits;
+            if shift_bits > 0 && word > shift_words {
+                shifted |= reachable[word - shift_words - 1] >> (64 - shift_bits);
+            }
+            let mut added = shifted & !reachable[word];
+            reachable[word] |= added;
+            while added != 0 {
+                let total = word * 64 + added.trailing_zeros() as usize;
+                if total <= upper_bound {
+                    reached_by.insert(total, idx);
+                    if reached_by.len() > MAX_STATES {
+                        return Err(PlanError::TooLarge);
+                    }
+                }
+                added &= added - 1;
+            }
+        }
+    }
+
+    let mut total = *reached_by
+        .keys()
+        .filter(|&&total| total >= to_free)
+        .min()
+        .ok_or(PlanError::Unreachable)?;
+    let mut selected = HashSet::new();
+    while total > 0 {
+        let file = files[reached_by[&total]];
+        selected.insert(file);
+        total -= file_system.size(file);
+    }
+    let mut plan = Vec::new();
+    collapse(file_system, ROOT, &selected, &mut plan);
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data, solve_part_2};
+
+    fn file_system(file_path: &str) -> FileSystem {
+        FileSystem::from_commands(&parse_data(load_file(file_path))).0
+    }
+
+    fn to_free(file_system: &FileSystem) -> usize {
+        30000000 - (70000000 - file_system.size(ROOT))
+    }
+
+    fn freed(file_system: &FileSystem, plan: &[NodeId]) -> usize {
+        plan.iter().map(|&id| file_system.size(id)).sum()
+    }
+
+    #[test]
+    fn files_can_beat_whole_directories() {
+        let file_system = file_system("./resources/test_data.txt");
+        let plan = plan(&file_system, to_free(&file_system)).unwrap();
+        let paths = plan
+            .iter()
+            .map(|&id| file_system.path(id))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/c.dat"]);
+        assert_eq!(freed(&file_system, &plan), 8504156);
+        assert!(freed(&file_system, &plan) < solve_part_2("./resources/test_data.txt"));
+    }
+
+    #[test]
+    fn fully_deleted_directories_are_collapsed() {
+        let file_system = file_system("./resources/test_data.txt");
+        let plan = plan(&file_system, 94853 + 14848514).unwrap();
+        let paths = plan
+            .iter()
+            .map(|&id| file_system.path(id))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/a", "/b.txt"]);
+        assert_eq!(
+            super::plan(&file_system, 584).map(|plan| freed(&file_system, &plan)),
+            Ok(584)
+        );
+        assert_eq!(super::plan(&file_system, 0), Ok(vec![]));
+        assert_eq!(
+            super::plan(&file_system, 48381166),
+            Err(PlanError::Unreachable)
+        );
+
+        let huge = FileSystem::from_commands(&parse_data(
+            "$ cd /\n$ ls\n300000000 disk.img\n".to_string(),
+        ))
+        .0;
+        assert_eq!(super::plan(&huge, 1), Err(PlanError::TooLarge));
+    }
+
+    #[test]
+    fn plan_never_frees_more_than_smallest_directory() {
+        let file_system = file_system("./resources/puzzle.txt");
+        let to_free = to_free(&file_system);
+        let plan = plan(&file_system, to_free).unwrap();
+        let freed = freed(&file_system, &plan);
+        assert!(freed >= to_free);
+        assert!(freed <= solve_part_2("./resources/puzzle.txt"));
+        for (idx, &first) in plan.iter().enumerate() {
+            for &second in &plan[idx + 1..] {
+                assert!(!file_system
+                    .path(second)
+                    .starts_with(&(file_system.path(first) + "/")));
+            }
+        }
+    }
+}
@@ -1,88 +1,193 @@
-use std::{collections::HashMap, fs};
+use std::{collections::HashMap, env, fmt, fs};
+
+use tree::FileSystem;
+
+mod deletion;
+mod query;
+mod render;
+mod tree;
 
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
 
-fn parse_data(data: String) -> Vec<Command> {
+fn try_parse_data(data: &str) -> Result<Vec<Command>, ParseError> {
     let mut result = vec![];
-    let lines = data.trim().lines().collect::<Vec<_>>();
+    let lines = data.trim_end().lines().collect::<Vec<_>>();
     let mut idx = 0;
-    while let Some(line) = Command::parse(&lines, &mut idx) {
+    while let Some(line) = Command::parse(&lines, &mut idx)? {
         result.push(line);
     }
 
-    result
+    Ok(result)
+}
+
+fn parse_data(data: String) -> Vec<Command> {
+    try_parse_data(&data).unwrap_or_else(|error| panic!("{}", error))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum Command {
     Cd { arg: String },
     Ls { output: Vec<String> },
+    Rm { paths: Vec<String> },
+    Mkdir { paths: Vec<String> },
+    Touch { paths: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseError {
+    UnknownCommand { line: usize, command: String },
+    MissingArgument { line: usize, command: String },
+    UnexpectedOutput { line: usize },
+    UnexpectedArgument { line: usize, command: String },
 }
 
 impl Command {
-    pub fn parse(iter: &Vec<&str>, idx: &mut usize) -> Option<Self> {
+    pub fn parse(iter: &[&str], idx: &mut usize) -> Result<Option<Self>, ParseError> {
+        while *idx < iter.len() && iter[*idx].trim().is_empty() {
+            *idx += 1;
+        }
         if *idx >= iter.len() {
-            return None;
+            return Ok(None);
         }
         let line = iter[*idx];
         *idx += 1;
 
-        if Self::is_ls(line) {
-            return Some(Self::parse_ls(iter, idx));
-        } else if Self::is_cd(line) {
-            return Some(Self::parse_cd(line));
+        let Some(command_line) = line.strip_prefix("$ ") else {
+            return Err(ParseError::UnexpectedOutput { line: *idx });
+        };
+        let words = command_line.split_whitespace().collect::<Vec<_>>();
+        let paths = words
+            .iter()
+            .skip(1)
+            .filter(|word| !word.starts_with('-'))
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>();
+        let missing_argument = ParseError::MissingArgument {
+            line: *idx,
+            command: command_line.to_string(),
+        };
+        match words.first().copied() {
+            Some("ls") if !paths.is_empty() => Err(ParseError::UnexpectedArgument {
+                line: *idx,
+                command: command_line.to_string(),
+            }),
+            Some("ls") => Ok(Some(Self::parse_ls(iter, idx))),
+            Some("cd") => match command_line[2..].trim() {
+                "" => Err(missing_argument),
+                arg => Ok(Some(Self::Cd {
+                    arg: arg.to_string(),
+                })),
+            },
+            Some("rm" | "mkdir" | "touch") if paths.is_empty() => Err(missing_argument),
+            Some("rm") => Ok(Some(Self::Rm { paths })),
+            Some("mkdir") => Ok(Some(Self::Mkdir { paths })),
+            Some("touch") => Ok(Some(Self::Touch { paths })),
+            _ => Err(ParseError::UnknownCommand {
+                line: *idx,
+                command: command_line.to_string(),
+            }),
         }
-
-        None
     }
 
-    fn parse_ls(iter: &Vec<&str>, idx: &mut usize) -> Self {
+    fn parse_ls(iter: &[&str], idx: &mut usize) -> Self {
         let mut result = vec![];
 
         while *idx < iter.len() && !iter[*idx].starts_with("$") {
-            result.push(iter[*idx].to_owned());
+            if let Some(entry) = Self::parse_ls_entry(iter[*idx]) {
+                result.push(entry);
+            }
             *idx += 1;
         }
 
         Self::Ls { output: result }
     }
 
-    fn parse_cd(command: &str) -> Self {
-        Self::Cd {
-            arg: command.replace("$ cd ", ""),
+    fn parse_ls_entry(line: &str) -> Option<String> {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        match fields[..] {
+            [] | ["total", _] => None,
+            [permissions, _, _, _, size, _, _, _, ref name @ ..]
+                if Self::is_permissions(permissions) && !name.is_empty() =>
+            {
+                let name = name.join(" ");
+                if name == "." || name == ".." {
+                    None
+                } else if permissions.starts_with('d') {
+                    Some(format!("dir {}", name))
+                } else {
+                    Some(format!("{} {}", size, name))
+                }
+            }
+            _ => Some(line.to_owned()),
         }
     }
 
-    fn is_ls(line: &str) -> bool {
-        line == "$ ls"
+    fn is_permissions(field: &str) -> bool {
+        field.len() == 10
+            && field.starts_with(['-', 'd'])
+            && field[1..].chars().all(|mode| "rwxsStT-".contains(mode))
     }
+}
 
-    fn is_cd(line: &str) -> bool {
-        line.starts_with("$ cd")
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand { line, command } => {
+                write!(f, "line {}: unknown command {:?}", line, command)
+            }
+            Self::MissingArgument { line, command } => {
+                write!(f, "line {}: missing argument in {:?}", line, command)
+            }
+            Self::UnexpectedOutput { line } => {
+                write!(f, "line {}: output outside of any command", line)
+            }
+            Self::UnexpectedArgument { line, command } => {
+                write!(f, "line {}: unexpected argument in {:?}", line, command)
+            }
+        }
     }
 }
 
+fn resolve_path(current_dir: &[String], path: &str) -> Vec<String> {
+    let mut resolved = if path.starts_with('/') {
+        vec!["/".to_string()]
+    } else {
+        current_dir.to_vec()
+    };
+    for name in tree::segments(path) {
+        match name {
+            ".." => {
+                if resolved.len() > 1 {
+                    resolved.pop();
+                }
+            }
+            _ => resolved.push(name.to_string()),
+        }
+    }
+    resolved
+}
+
 fn get_files(commands: Vec<Command>) -> HashMap<Vec<String>, usize> {
-    let mut current_dir = vec![];
+    let mut current_dir = vec!["/".to_string()];
     let mut files = HashMap::new();
     commands.into_iter().for_each(|command| match command {
-        Command::Cd { arg } => match arg.as_str() {
-            "/" => current_dir = vec!["/".to_string()],
-            ".." => {
-                current_dir.pop();
-            }
-            _ => current_dir.push(arg),
-        },
+        Command::Cd { arg } => current_dir = resolve_path(&current_dir, &arg),
         Command::Ls { output } => output.into_iter().for_each(|ls| {
-            let split = ls.trim().split_whitespace().collect::<Vec<_>>();
-            if let Some(file_size) = split[0].parse::<usize>().ok() {
-                let mut file_path = current_dir.clone();
-                file_path.push(split[1].to_owned());
-                files.insert(file_path, file_size);
+            if let [size, name] = ls.splitn(2, ' ').collect::<Vec<_>>()[..] {
+                if let Ok(file_size) = size.parse::<usize>() {
+                    let mut file_path = current_dir.clone();
+                    file_path.push(name.to_owned());
+                    files.insert(file_path, file_size);
+                }
             }
         }),
+        Command::Rm { paths } => paths.into_iter().for_each(|path| {
+            let removed = resolve_path(&current_dir, &path);
+            files.retain(|file_path, _| !file_path.starts_with(&removed));
+        }),
+        Command::Mkdir { .. } | Command::Touch { .. } => {}
     });
 
     files
@@ -110,14 +215,8 @@ fn sum_directories_with_size_at_most(
     size: usize,
 ) -> usize {
     directories_size
-        .into_iter()
-        .filter_map(|(_, directory_size)| {
-            if directory_size < size {
-                Some(directory_size)
-            } else {
-                None
-            }
-        })
+        .into_values()
+        .filter(|&directory_size| directory_size < size)
         .sum()
 }
 
@@ -126,14 +225,8 @@ fn find_smalest_dir_with_size_at_most(
     size: usize,
 ) -> usize {
     directories_size
-        .into_iter()
-        .filter_map(|(_, directory_size)| {
-            if directory_size >= size {
-                Some(directory_size)
-            } else {
-                None
-            }
-        })
+        .into_values()
+        .filter(|&directory_size| directory_size >= size)
         .min()
         .unwrap()
 }
@@ -152,10 +245,14 @@ pub fn solve_part_2(file_name: &str) -> usize {
     let files = get_files(commands);
     let directories = directories_size(files);
     let total_filesystem_size = 70000000;
-    let needed_size = 30000000;
+    let needed_size: usize = 30000000;
     let used_size = directories.get(&vec!["/".to_string()]).unwrap();
     let available_space = total_filesystem_size - used_size;
-    find_smalest_dir_with_size_at_most(directories, needed_size - available_space)
+    let to_free = needed_size.saturating_sub(available_space);
+    if to_free == 0 {
+        return 0;
+    }
+    find_smalest_dir_with_size_at_most(directories, to_free)
 }
 
 fn part_1(file_name: &str) {
@@ -168,10 +265,109 @@ fn part_2(file_name: &str) {
     println!("Part 2 result: {:?}", result);
 }
 
+fn check(file_name: &str, path: Option<String>) {
+    let data = load_file(file_name);
+    let commands = match try_parse_data(&data) {
+        Ok(commands) => commands,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    let (file_system, issues) = FileSystem::from_commands(&commands);
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    println!(
+        "{} issues, {} directories, {} files",
+        issues.len(),
+        file_system.directories().len(),
+        file_system.files().len()
+    );
+    if let Some(deepest) = file_system
+        .walk()
+        .into_iter()
+        .max_by_key(|&id| file_system.depth(id))
+    {
+        println!(
+            "Deepest entry: {} (depth {})",
+            file_system.path(deepest),
+            file_system.depth(deepest)
+        );
+    }
+    let path = path.unwrap_or("/".to_string());
+    match file_system.lookup(&path) {
+        Some(id) => println!("Size of {}: {}", path, file_system.size(id)),
+        None => println!("{} not found", path),
+    }
+}
+
+fn show(file_name: &str, format: &str) {
+    let data = load_file(file_name);
+    let commands = parse_data(data);
+    let (file_system, _) = FileSystem::from_commands(&commands);
+    match format {
+        "tree" => print!("{}", render::render_tree(&file_system)),
+        "du" => print!("{}", render::render_du(&file_system)),
+        _ => println!("{}", render::to_json(&file_system)),
+    }
+}
+
+fn free(file_name: &str, needed_size: Option<usize>) {
+    let data = load_file(file_name);
+    let commands = parse_data(data);
+    let (file_system, _) = FileSystem::from_commands(&commands);
+    let total_filesystem_size = 70000000;
+    let needed_size = needed_size.unwrap_or(30000000);
+    let available_space = total_filesystem_size - file_system.size(tree::ROOT);
+    let to_free = needed_size.saturating_sub(available_space);
+    match deletion::plan(&file_system, to_free) {
+        Ok(plan) => {
+            for &id in &plan {
+                println!("{}\t{}", file_system.size(id), file_system.path(id));
+            }
+            println!(
+                "Freed {} of {} needed",
+                plan.iter().map(|&id| file_system.size(id)).sum::<usize>(),
+                to_free
+            );
+        }
+        Err(error) => println!("Cannot free {}: {:?}", to_free, error),
+    }
+}
+
+fn run_query(file_name: &str, query: &str) {
+    let data = load_file(file_name);
+    let commands = parse_data(data);
+    let (file_system, _) = FileSystem::from_commands(&commands);
+    match query::parse(query) {
+        Ok(query) => {
+            let ids = query.run(&file_system);
+            print!("{}", query::render_table(&file_system, &ids));
+            println!("{} rows", ids.len());
+        }
+        Err(error) => println!("{}", error),
+    }
+}
+
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    match env::args().nth(1).as_deref() {
+        Some(format @ ("tree" | "du" | "json")) => show(FILE_PATH, format),
+        Some("free") => free(
+            FILE_PATH,
+            env::args().nth(2).and_then(|arg| arg.parse().ok()),
+        ),
+        Some("query") => run_query(
+            FILE_PATH,
+            &env::args().skip(2).collect::<Vec<_>>().join(" "),
+        ),
+        Some("check") => check(FILE_PATH, env::args().nth(2)),
+        _ => {
+            part_1(FILE_PATH);
+            part_2(FILE_PATH);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -234,6 +430,83 @@ mod tests {
         )
     }
 
+    #[test]
+    fn load_shell_capture() {
+        let commands = parse_data(load_file("./resources/test_data_shell.txt"));
+        let expected = parse_data(load_file("./resources/test_data.txt"));
+        assert_eq!(commands[1], expected[1]);
+        assert_eq!(
+            commands[2],
+            Command::Cd {
+                arg: "a/e".to_string()
+            }
+        );
+        assert_eq!(
+            commands[5],
+            Command::Ls {
+                output: vec![
+                    "4060174 j".to_string(),
+                    "8033020 d.log".to_string(),
+                    "5626152 d.ext".to_string(),
+                    "7214296 k".to_string()
+                ]
+            }
+        );
+        assert_eq!(
+            commands[8..11],
+            [
+                Command::Mkdir {
+                    paths: vec!["tmp/cache".to_string()]
+                },
+                Command::Touch {
+                    paths: vec!["tmp/cache/lock".to_string(), "./new.log".to_string()]
+                },
+                Command::Rm {
+                    paths: vec!["tmp".to_string(), "/c.dat".to_string()]
+                },
+            ]
+        );
+        assert_eq!(solve_part_1("./resources/test_data_shell.txt"), 95437);
+        assert_eq!(solve_part_2("./resources/test_data_shell.txt"), 0);
+        let directories = directories_size(get_files(commands));
+        assert_eq!(directories[&vec!["/".to_string()]], 48381165 - 8504156);
+        assert_eq!(
+            resolve_path(&["/".to_string(), "d".to_string()], "../a/./e/"),
+            ["/", "a", "e"]
+        );
+    }
+
+    #[test]
+    fn unknown_commands_are_errors() {
+        let error = try_parse_data("$ cd /\n$ ls\n1 a\n\n$ cat a\n$ ls\n").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::UnknownCommand {
+                line: 5,
+                command: "cat a".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "line 5: unknown command \"cat a\"");
+        assert_eq!(
+            try_parse_data("$ cd /\n$ rm -r\n"),
+            Err(ParseError::MissingArgument {
+                line: 2,
+                command: "rm -r".to_string()
+            })
+        );
+        assert_eq!(
+            try_parse_data("1 a\n$ ls\n"),
+            Err(ParseError::UnexpectedOutput { line: 1 })
+        );
+        assert_eq!(
+            try_parse_data("$ cd /\n$ ls -l a\n"),
+            Err(ParseError::UnexpectedArgument {
+                line: 2,
+                command: "ls -l a".to_string()
+            })
+        );
+    }
+
     #[test]
     fn test_part_1() {
         let result = solve_part_1("./resources/test_data.txt");
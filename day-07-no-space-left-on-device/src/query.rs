@@ -0,0 +1,669 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::tree::{FileSystem, NodeId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Dirs,
+    Files,
+    Entries,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Size,
+    Depth,
+    Name,
+    Path,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(usize),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Compare {
+        field: Field,
+        comparison: Comparison,
+        value: Value,
+    },
+    Matching {
+        field: Field,
+        pattern: String,
+    },
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub target: Target,
+    pub condition: Option<Condition>,
+    pub order: Option<(Field, bool)>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnterminatedString {
+        column: usize,
+    },
+    UnexpectedToken {
+        column: usize,
+        found: String,
+        expected: &'static str,
+    },
+    UnexpectedEnd {
+        expected: &'static str,
+    },
+    TypeMismatch {
+        column: usize,
+        field: Field,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Word(String),
+    Number(usize),
+    Text(String),
+    Symbol(&'static str),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+const SYMBOLS: [&str; 8] = ["<=", ">=", "!=", "<", ">", "=", "(", ")"];
+
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryError> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let column = idx + 1;
+        if chars[idx].is_whitespace() {
+            idx += 1;
+            continue;
+        }
+        if chars[idx] == '\'' || chars[idx] == '"' {
+            let quote = chars[idx];
+            let end = (idx + 1..chars.len())
+                .find(|&end| chars[end] == quote)
+                .ok_or(QueryError::UnterminatedString { column })?;
+            tokens.push(Token {
+                kind: TokenKind::Text(chars[idx + 1..end].iter().collect()),
+                column,
+            });
+            idx = end + 1;
+            continue;
+        }
+        let rest = chars[idx..].iter().take(2).collect::<String>();
+        if let Some(symbol) = SYMBOLS.into_iter().find(|symbol| rest.starts_with(symbol)) {
+            tokens.push(Token {
+                kind: TokenKind::Symbol(symbol),
+                column,
+            });
+            idx += symbol.len();
+            continue;
+        }
+        let word = chars[idx..]
+            .iter()
+            .take_while(|&&character| {
+                !character.is_whitespace() && !"<>=!()'\"".contains(character)
+            })
+            .collect::<String>();
+        if word.is_empty() {
+            return Err(QueryError::UnexpectedToken {
+                column,
+                found: chars[idx].to_string(),
+                expected: "a word, number or operator",
+            });
+        }
+        idx += word.chars().count();
+        let kind = match word.parse() {
+            Ok(number) => TokenKind::Number(number),
+            Err(_) => TokenKind::Word(word),
+        };
+        tokens.push(Token { kind, column });
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<Token, QueryError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(QueryError::UnexpectedEnd { expected })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn unexpected(token: &Token, expected: &'static str) -> QueryError {
+        QueryError::UnexpectedToken {
+            column: token.column,
+            found: match &token.kind {
+                TokenKind::Word(word) => word.clone(),
+                TokenKind::Number(number) => number.to_string(),
+                TokenKind::Text(text) => format!("'{}'", text),
+                TokenKind::Symbol(symbol) => symbol.to_string(),
+            },
+            expected,
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(TokenKind::Word(word)) if word.eq_ignore_ascii_case(keyword))
+        {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_keyword(&mut self, keyword: &'static str) -> Result<(), QueryError> {
+        let token = self.next(keyword)?;
+        match token.kind {
+            TokenKind::Word(word) if word.eq_ignore_ascii_case(keyword) => Ok(()),
+            _ => Err(Self::unexpected(&token, keyword)),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, QueryError> {
+        let token = self.next("a number")?;
+        match token.kind {
+            TokenKind::Number(number) => Ok(number),
+            _ => Err(Self::unexpected(&token, "a number")),
+        }
+    }
+
+    fn text(&mut self) -> Result<String, QueryError> {
+        let token = self.next("a quoted pattern")?;
+        match token.kind {
+            TokenKind::Text(text) | TokenKind::Word(text) => Ok(text),
+            _ => Err(Self::unexpected(&token, "a quoted pattern")),
+        }
+    }
+
+    fn field(&mut self) -> Result<(Field, usize), QueryError> {
+        const EXPECTED: &str = "size, depth, name or path";
+        let token = self.next(EXPECTED)?;
+        let field = match &token.kind {
+            TokenKind::Word(word) => match word.to_lowercase().as_str() {
+                "size" => Field::Size,
+                "depth" => Field::Depth,
+                "name" => Field::Name,
+                "path" => Field::Path,
+                _ => return Err(Self::unexpected(&token, EXPECTED)),
+            },
+            _ => return Err(Self::unexpected(&token, EXPECTED)),
+        };
+        Ok((field, token.column))
+    }
+
+    fn query(&mut self) -> Result<Query, QueryError> {
+        const TARGETS: &str = "dirs, files or entries";
+        self.keyword("find");
+        let token = self.next(TARGETS)?;
+        let target = match &token.kind {
+            TokenKind::Word(word) => match word.to_lowercase().as_str() {
+                "dirs" | "directories" => Target::Dirs,
+                "files" => Target::Files,
+                "entries" | "all" => Target::Entries,
+                _ => return Err(Self::unexpected(&token, TARGETS)),
+            },
+            _ => return Err(Self::unexpected(&token, TARGETS)),
+        };
+        let mut condition = None;
+        if self.keyword("matching") {
+            condition = Some(Condition::Matching {
+                field: Field::Name,
+                pattern: self.text()?,
+            });
+        }
+        if self.keyword("where") {
+            let filter = self.or()?;
+            condition = Some(match condition {
+                Some(matching) => Condition::And(Box::new(matching), Box::new(filter)),
+                None => filter,
+            });
+        }
+        let mut order = None;
+        if self.keyword("order") {
+            self.expect_keyword("by")?;
+            let (field, _) = self.field()?;
+            let descending = self.keyword("desc");
+            if !descending {
+                self.keyword("asc");
+            }
+            order = Some((field, descending));
+        }
+        let mut limit = None;
+        if self.keyword("limit") {
+            limit = Some(self.number()?);
+        }
+        if let Some(token) = self.tokens.get(self.position) {
+            return Err(Self::unexpected(token, "end of query"));
+        }
+        Ok(Query {
+            target,
+            condition,
+            order,
+            limit,
+        })
+    }
+
+    fn or(&mut self) -> Result<Condition, QueryError> {
+        let mut condition = self.and()?;
+        while self.keyword("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, QueryError> {
+        let mut condition = self.atom()?;
+        while self.keyword("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.atom()?));
+        }
+        Ok(condition)
+    }
+
+    fn atom(&mut self) -> Result<Condition, QueryError> {
+        if self.keyword("not") {
+            return Ok(Condition::Not(Box::new(self.atom()?)));
+        }
+        if self.peek() == Some(&TokenKind::Symbol("(")) {
+            self.position += 1;
+            let condition = self.or()?;
+            let token = self.next(")")?;
+            if token.kind != TokenKind::Symbol(")") {
+                return Err(Self::unexpected(&token, ")"));
+            }
+            return Ok(condition);
+        }
+        let (field, column) = self.field()?;
+        if self.keyword("matching") {
+            return Ok(Condition::Matching {
+                field,
+                pattern: self.text()?,
+            });
+        }
+        const OPERATORS: &str = "a comparison or matching";
+        let token = self.next(OPERATORS)?;
+        let comparison = match token.kind {
+            TokenKind::Symbol("<") => Comparison::Less,
+            TokenKind::Symbol("<=") => Comparison::LessOrEqual,
+            TokenKind::Symbol(">") => Comparison::Greater,
+            TokenKind::Symbol(">=") => Comparison::GreaterOrEqual,
+            TokenKind::Symbol("=") => Comparison::Equal,
+            TokenKind::Symbol("!=") => Comparison::NotEqual,
+            _ => return Err(Self::unexpected(&token, OPERATORS)),
+        };
+        let token = self.next("a value")?;
+        let value = match token.kind {
+            TokenKind::Number(number) => Value::Number(number),
+            TokenKind::Text(text) => Value::Text(text),
+            _ => return Err(Self::unexpected(&token, "a value")),
+        };
+        let numeric = matches!(field, Field::Size | Field::Depth);
+        if numeric != matches!(value, Value::Number(_)) {
+            return Err(QueryError::TypeMismatch { column, field });
+        }
+        Ok(Condition::Compare {
+            field,
+            comparison,
+            value,
+        })
+    }
+}
+
+pub fn parse(query: &str) -> Result<Query, QueryError> {
+    Parser {
+        tokens: tokenize(query)?,
+        position: 0,
+    }
+    .query()
+}
+
+pub fn glob(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let mut matched = vec![false; text.len() + 1];
+    matched[0] = true;
+    for &wildcard in &pattern {
+        let mut next = vec![false; text.len() + 1];
+        for idx in 0..=text.len() {
+            next[idx] = match wildcard {
+                '*' => matched[idx] || (idx > 0 && next[idx - 1]),
+                '?' => idx > 0 && matched[idx - 1],
+                character => idx > 0 && matched[idx - 1] && text[idx - 1] == character,
+            };
+        }
+        matched = next;
+    }
+    matched[text.len()]
+}
+
+fn value(file_system: &FileSystem, id: NodeId, field: Field) -> Value {
+    match field {
+        Field::Size => Value::Number(file_system.size(id)),
+        Field::Depth => Value::Number(file_system.depth(id)),
+        Field::Name => Value::Text(file_system.node(id).name.clone()),
+        Field::Path => Value::Text(file_system.path(id)),
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.cmp(right),
+        (Value::Text(left), Value::Text(right)) => left.cmp(right),
+        (Value::Number(_), Value::Text(_)) => Ordering::Less,
+        (Value::Text(_), Value::Number(_)) => Ordering::Greater,
+    }
+}
+
+impl Condition {
+    pub fn holds(&self, file_system: &FileSystem, id: NodeId) -> bool {
+        match self {
+            Self::Compare {
+                field,
+                comparison,
+                value: expected,
+            } => {
+                let ordering = compare(&value(file_system, id, *field), expected);
+                match comparison {
+                    Comparison::Less => ordering.is_lt(),
+                    Comparison::LessOrEqual => ordering.is_le(),
+                    Comparison::Greater => ordering.is_gt(),
+                    Comparison::GreaterOrEqual => ordering.is_ge(),
+                    Comparison::Equal => ordering.is_eq(),
+                    Comparison::NotEqual => ordering.is_ne(),
+                }
+            }
+            Self::Matching { field, pattern } => match value(file_system, id, *field) {
+                Value::Text(text) => glob(pattern, &text),
+                Value::Number(number) => glob(pattern, &number.to_string()),
+            },
+            Self::Not(condition) => !condition.holds(file_system, id),
+            Self::And(left, right) => left.holds(file_system, id) && right.holds(file_system, id),
+            Self::Or(left, right) => left.holds(file_system, id) || right.holds(file_system, id),
+        }
+    }
+}
+
+impl Query {
+    pub fn run(&self, file_system: &FileSystem) -> Vec<NodeId> {
+        let mut ids = match self.target {
+            Target::Dirs => file_system.directories(),
+            Target::Files => file_system.files(),
+            Target::Entries => file_system.walk(),
+        };
+        if let Some(condition) = &self.condition {
+            ids.retain(|&id| condition.holds(file_system, id));
+        }
+        if let Some((field, descending)) = self.order {
+            ids.sort_by(|&left, &right| {
+                let ordering = compare(
+                    &value(file_system, left, field),
+                    &value(file_system, right, field),
+                );
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        if let Some(limit) = self.limit {
+            ids.truncate(limit);
+        }
+        ids
+    }
+}
+
+pub fn render_table(file_system: &FileSystem, ids: &[NodeId]) -> String {
+    let rows = ids
+        .iter()
+        .map(|&id| {
+            [
+                if file_system.is_dir(id) {
+                    "dir"
+                } else {
+                    "file"
+                }
+                .to_string(),
+                file_system.size(id).to_string(),
+                file_system.depth(id).to_string(),
+                file_system.path(id),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = ["type", "size", "depth", "path"].map(str::to_string);
+    let widths = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let line = format!(
+            "{:<w0$}  {:>w1$}  {:>w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Size => "size",
+            Self::Depth => "depth",
+            Self::Name => "name",
+            Self::Path => "path",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedString { column } => {
+                write!(f, "column {}: unterminated string", column)
+            }
+            Self::UnexpectedToken {
+                column,
+                found,
+                expected,
+            } => write!(
+                f,
+                "column {}: expected {}, found {}",
+                column, expected, found
+            ),
+            Self::UnexpectedEnd { expected } => {
+                write!(f, "expected {}, found end of query", expected)
+            }
+            Self::TypeMismatch { column, field } => {
+                write!(f, "column {}: {} compared with wrong type", column, field)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data, solve_part_1, solve_part_2};
+
+    fn file_system(file_path: &str) -> FileSystem {
+        FileSystem::from_commands(&parse_data(load_file(file_path))).0
+    }
+
+    fn paths(file_system: &FileSystem, query: &str) -> Vec<String> {
+        parse(query)
+            .unwrap()
+            .run(file_system)
+            .into_iter()
+            .map(|id| file_system.path(id))
+            .collect()
+    }
+
+    #[test]
+    fn queries_reproduce_both_parts() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let file_system = file_system(file_path);
+            let small = parse("find dirs where size < 100000").unwrap();
+            let sum = small
+                .run(&file_system)
+                .into_iter()
+                .map(|id| file_system.size(id))
+                .sum::<usize>();
+            assert_eq!(sum, solve_part_1(file_path));
+
+            let to_free = 30000000 - (70000000 - file_system.size(0));
+            let smallest = parse(&format!(
+                "find dirs where size >= {} order by size limit 1",
+                to_free
+            ))
+            .unwrap()
+            .run(&file_system);
+            assert_eq!(file_system.size(smallest[0]), solve_part_2(file_path));
+        }
+    }
+
+    #[test]
+    fn filters_order_and_limit() {
+        let file_system = file_system("./resources/test_data.txt");
+        assert_eq!(
+            paths(&file_system, "files matching '*.log'"),
+            vec!["/d/d.log"]
+        );
+        assert_eq!(
+            paths(
+                &file_system,
+                "FIND entries where depth >= 2 and not (name = 'e' or size > 60000) order by name desc"
+            ),
+            vec!["/a/e/i", "/a/g", "/a/f"]
+        );
+        assert_eq!(
+            paths(
+                &file_system,
+                "find files where path matching '/d/*' order by size desc limit 2"
+            ),
+            vec!["/d/d.log", "/d/k"]
+        );
+        assert_eq!(
+            parse("files matching '*.???'")
+                .unwrap()
+                .run(&file_system)
+                .len(),
+            5
+        );
+        assert!(glob("*a*b?", "xxaxxbz"));
+        assert!(!glob("*a*b?", "xxaxxb"));
+    }
+
+    #[test]
+    fn unquoted_patterns_keep_their_case() {
+        let commands = parse_data("$ cd /\n$ ls\n10 Makefile\n20 makefile\n".to_string());
+        let file_system = FileSystem::from_commands(&commands).0;
+        assert_eq!(
+            paths(&file_system, "FILES MATCHING Makefile"),
+            vec!["/Makefile"]
+        );
+        assert_eq!(
+            paths(&file_system, "files matching Makefile"),
+            paths(&file_system, "files matching 'Makefile'")
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_column() {
+        assert_eq!(
+            parse("find dirs where size <= 'big'"),
+            Err(QueryError::TypeMismatch {
+                column: 17,
+                field: Field::Size
+            })
+        );
+        assert_eq!(
+            parse("find folders").unwrap_err().to_string(),
+            "column 6: expected dirs, files or entries, found folders"
+        );
+        assert_eq!(
+            parse("files where name = 'a"),
+            Err(QueryError::UnterminatedString { column: 20 })
+        );
+        assert_eq!(
+            parse("dirs order size"),
+            Err(QueryError::UnexpectedToken {
+                column: 12,
+                found: "size".to_string(),
+                expected: "by"
+            })
+        );
+        assert_eq!(
+            parse("dirs limit"),
+            Err(QueryError::UnexpectedEnd {
+                expected: "a number"
+            })
+        );
+    }
+
+    #[test]
+    fn table_is_aligned() {
+        let file_system = file_system("./resources/test_data.txt");
+        let ids = parse("dirs order by size desc").unwrap().run(&file_system);
+        assert_eq!(
+            render_table(&file_system, &ids),
+            "\
+type      size  depth  path
+dir   48381165      0  /
+dir   24933642      1  /d
+dir      94853      1  /a
+dir        584      2  /a/e
+"
+        );
+    }
+}
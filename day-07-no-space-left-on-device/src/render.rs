@@ -0,0 +1,163 @@
+use std::fmt::Write;
+
+use crate::tree::{FileSystem, NodeId, NodeKind, ROOT};
+
+const UNITS: [&str; 5] = ["", "K", "M", "G", "T"];
+
+pub fn human_size(size: usize) -> String {
+    let mut unit = 0;
+    let mut scaled = size as f64;
+    while scaled >= 1024.0 && unit + 1 < UNITS.len() {
+        scaled /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        size.to_string()
+    } else if scaled < 10.0 {
+        format!("{:.1}{}", (scaled * 10.0).ceil() / 10.0, UNITS[unit])
+    } else {
+        format!("{}{}", scaled.ceil(), UNITS[unit])
+    }
+}
+
+fn tree_lines(file_system: &FileSystem, id: NodeId, depth: usize, lines: &mut String) {
+    let node = file_system.node(id);
+    let indent = "  ".repeat(depth);
+    match node.kind {
+        NodeKind::Directory(_) => writeln!(lines, "{}- {} (dir)", indent, node.name).unwrap(),
+        NodeKind::File(size) => {
+            writeln!(lines, "{}- {} (file, size={})", indent, node.name, size).unwrap()
+        }
+    }
+    for child in file_system.children(id) {
+        tree_lines(file_system, child, depth + 1, lines);
+    }
+}
+
+pub fn render_tree(file_system: &FileSystem) -> String {
+    let mut lines = String::new();
+    tree_lines(file_system, ROOT, 0, &mut lines);
+    lines
+}
+
+pub fn render_du(file_system: &FileSystem) -> String {
+    let mut directories = file_system.directories();
+    directories.sort_by_key(|&id| {
+        (
+            std::cmp::Reverse(file_system.size(id)),
+            file_system.path(id),
+        )
+    });
+    directories
+        .into_iter()
+        .map(|id| {
+            format!(
+                "{}\t{}\n",
+                human_size(file_system.size(id)),
+                file_system.path(id)
+            )
+        })
+        .collect()
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                write!(escaped, "\\u{:04x}", character as u32).unwrap()
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_node(file_system: &FileSystem, id: NodeId, json: &mut String) {
+    let node = file_system.node(id);
+    write!(json, "{{\"name\":{},", json_string(&node.name)).unwrap();
+    match node.kind {
+        NodeKind::File(size) => write!(json, "\"type\":\"file\",\"size\":{}}}", size).unwrap(),
+        NodeKind::Directory(_) => {
+            write!(
+                json,
+                "\"type\":\"dir\",\"size\":{},\"children\":[",
+                file_system.size(id)
+            )
+            .unwrap();
+            for (idx, child) in file_system.children(id).into_iter().enumerate() {
+                if idx > 0 {
+                    json.push(',');
+                }
+                json_node(file_system, child, json);
+            }
+            json.push_str("]}");
+        }
+    }
+}
+
+pub fn to_json(file_system: &FileSystem) -> String {
+    let mut json = String::new();
+    json_node(file_system, ROOT, &mut json);
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_file, parse_data};
+
+    fn test_file_system() -> FileSystem {
+        FileSystem::from_commands(&parse_data(load_file("./resources/test_data.txt"))).0
+    }
+
+    #[test]
+    fn tree_matches_puzzle_notation() {
+        let expected = "\
+- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+";
+        assert_eq!(render_tree(&test_file_system()), expected);
+    }
+
+    #[test]
+    fn du_lists_directories_by_size() {
+        assert_eq!(
+            render_du(&test_file_system()),
+            "47M\t/\n24M\t/d\n93K\t/a\n584\t/a/e\n"
+        );
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1536), "1.5K");
+        assert_eq!(human_size(1537), "1.6K");
+        assert_eq!(human_size(10 * 1024 + 1), "11K");
+    }
+
+    #[test]
+    fn json_export_of_hierarchy() {
+        let json = to_json(&test_file_system());
+        assert!(
+            json.starts_with("{\"name\":\"/\",\"type\":\"dir\",\"size\":48381165,\"children\":[")
+        );
+        assert!(json.contains(
+            "{\"name\":\"e\",\"type\":\"dir\",\"size\":584,\"children\":[{\"name\":\"i\",\"type\":\"file\",\"size\":584}]}"
+        ));
+        assert_eq!(json.matches("\"type\":\"file\"").count(), 10);
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+    }
+}
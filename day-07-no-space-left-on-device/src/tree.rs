@@ -0,0 +1,656 @@
+use std::{cell::Cell, collections::BTreeMap, fmt};
+
+use crate::Command;
+
+pub type NodeId = usize;
+
+pub const ROOT: NodeId = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeKind {
+    Directory(BTreeMap<String, NodeId>),
+    File(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    pub kind: NodeKind,
+    size: Cell<Option<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    UnlistedDirectory {
+        command: usize,
+        path: String,
+    },
+    AboveRoot {
+        command: usize,
+    },
+    ConflictingSize {
+        command: usize,
+        path: String,
+        previous: usize,
+        size: usize,
+    },
+    KindConflict {
+        command: usize,
+        path: String,
+    },
+    MalformedEntry {
+        command: usize,
+        entry: String,
+    },
+    MissingEntry {
+        command: usize,
+        path: String,
+    },
+    RemovedWorkingDirectory {
+        command: usize,
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                name: "/".to_string(),
+                parent: None,
+                kind: NodeKind::Directory(BTreeMap::new()),
+                size: Cell::new(None),
+            }],
+        }
+    }
+
+    pub fn from_commands(commands: &[Command]) -> (Self, Vec<Issue>) {
+        let mut file_system = Self::new();
+        let mut issues = Vec::new();
+        let mut current = ROOT;
+        for (command_idx, command) in commands.iter().enumerate() {
+            let command_number = command_idx + 1;
+            match command {
+                Command::Cd { arg } => {
+                    if arg.starts_with('/') {
+                        current = ROOT;
+                    }
+                    for name in segments(arg) {
+                        match name {
+                            ".." => match file_system.parent(current) {
+                                Some(parent) => current = parent,
+                                None => issues.push(Issue::AboveRoot {
+                                    command: command_number,
+                                }),
+                            },
+                            name => match file_system.child(current, name) {
+                                Some(child) if file_system.is_dir(child) => current = child,
+                                Some(child) => {
+                                    issues.push(Issue::KindConflict {
+                                        command: command_number,
+                                        path: file_system.path(child),
+                                    });
+                                    break;
+                                }
+                                None => {
+                                    current = file_system.add_directory(current, name);
+                                    issues.push(Issue::UnlistedDirectory {
+                                        command: command_number,
+                                        path: file_system.path(current),
+                                    });
+                                }
+                            },
+                        }
+                    }
+                }
+                Command::Ls { output } => {
+                    for entry in output {
+                        let issue = match entry.splitn(2, ' ').collect::<Vec<_>>()[..] {
+                            ["dir", name] => file_system.list_directory(current, name),
+                            [size, name] => match size.parse() {
+                                Ok(size) => file_system.list_file(current, name, size),
+                                Err(_) => Err(Issue::MalformedEntry {
+                                    command: 0,
+                                    entry: entry.clone(),
+                                }),
+                            },
+                            _ => Err(Issue::MalformedEntry {
+                                command: 0,
+                                entry: entry.clone(),
+                            }),
+                        };
+                        if let Err(issue) = issue {
+                            issues.push(issue.at(command_number));
+                        }
+                    }
+                }
+                Command::Rm { paths } => {
+                    for path in paths {
+                        match file_system.resolve(current, path) {
+                            Some(id) => {
+                                if current != ROOT && file_system.is_within(current, id) {
+                                    issues.push(Issue::RemovedWorkingDirectory {
+                                        command: command_number,
+                                        path: file_system.path(current),
+                                    });
+                                    current = file_system.parent(id).unwrap_or(ROOT);
+                                }
+                                file_system.remove(id);
+                            }
+                            None => issues.push(Issue::MissingEntry {
+                                command: command_number,
+                                path: path.clone(),
+                            }),
+                        }
+                    }
+                }
+                Command::Mkdir { paths } => {
+                    for path in paths {
+                        if let Err(issue) = file_system.make_directories(current, path) {
+                            issues.push(issue.at(command_number));
+                        }
+                    }
+                }
+                Command::Touch { paths } => {
+                    for path in paths {
+                        if let Err(issue) = file_system.touch(current, path) {
+                            issues.push(issue.at(command_number));
+                        }
+                    }
+                }
+            }
+        }
+        (file_system, issues)
+    }
+
+    fn push_node(&mut self, parent: NodeId, name: &str, kind: NodeKind) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(parent),
+            kind,
+            size: Cell::new(None),
+        });
+        if let NodeKind::Directory(children) = &mut self.nodes[parent].kind {
+            children.insert(name.to_string(), id);
+        }
+        self.invalidate(parent);
+        id
+    }
+
+    fn invalidate(&self, id: NodeId) {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            self.nodes[id].size.set(None);
+            current = self.nodes[id].parent;
+        }
+    }
+
+    fn add_directory(&mut self, parent: NodeId, name: &str) -> NodeId {
+        self.push_node(parent, name, NodeKind::Directory(BTreeMap::new()))
+    }
+
+    fn list_directory(&mut self, parent: NodeId, name: &str) -> Result<NodeId, Issue> {
+        match self.child(parent, name) {
+            Some(child) if self.is_dir(child) => Ok(child),
+            Some(child) => Err(Issue::KindConflict {
+                command: 0,
+                path: self.path(child),
+            }),
+            None => Ok(self.add_directory(parent, name)),
+        }
+    }
+
+    fn list_file(&mut self, parent: NodeId, name: &str, size: usize) -> Result<NodeId, Issue> {
+        let Some(child) = self.child(parent, name) else {
+            return Ok(self.push_node(parent, name, NodeKind::File(size)));
+        };
+        match self.nodes[child].kind {
+            NodeKind::File(previous) if previous != size => {
+                self.nodes[child].kind = NodeKind::File(size);
+                self.invalidate(child);
+                Err(Issue::ConflictingSize {
+                    command: 0,
+                    path: self.path(child),
+                    previous,
+                    size,
+                })
+            }
+            NodeKind::File(_) => Ok(child),
+            NodeKind::Directory(_) => Err(Issue::KindConflict {
+                command: 0,
+                path: self.path(child),
+            }),
+        }
+    }
+
+    fn remove(&mut self, id: NodeId) {
+        let Some(parent) = self.nodes[id].parent else {
+            for child in self.children(ROOT) {
+                self.nodes[child].parent = None;
+            }
+            self.nodes[ROOT].kind = NodeKind::Directory(BTreeMap::new());
+            self.invalidate(ROOT);
+            return;
+        };
+        let name = self.nodes[id].name.clone();
+        if let NodeKind::Directory(children) = &mut self.nodes[parent].kind {
+            children.remove(&name);
+        }
+        self.nodes[id].parent = None;
+        self.invalidate(parent);
+    }
+
+    fn is_within(&self, id: NodeId, ancestor: NodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.nodes[id].parent;
+        }
+        false
+    }
+
+    fn make_directories(&mut self, from: NodeId, path: &str) -> Result<NodeId, Issue> {
+        let mut current = if path.starts_with('/') { ROOT } else { from };
+        for name in segments(path) {
+            current = match name {
+                ".." => self.parent(current).unwrap_or(ROOT),
+                name => self.list_directory(current, name)?,
+            };
+        }
+        Ok(current)
+    }
+
+    fn touch(&mut self, from: NodeId, path: &str) -> Result<NodeId, Issue> {
+        let missing = Issue::MissingEntry {
+            command: 0,
+            path: path.to_string(),
+        };
+        let trimmed = path.trim_end_matches('/');
+        let (directory, name) = match trimmed.rsplit_once('/') {
+            Some(("", name)) => (Some(ROOT), name),
+            Some((directory, name)) => (self.resolve(from, directory), name),
+            None => (Some(from), trimmed),
+        };
+        let directory = directory.ok_or(missing.clone())?;
+        if !self.is_dir(directory) {
+            return Err(Issue::KindConflict {
+                command: 0,
+                path: self.path(directory),
+            });
+        }
+        match self.child(directory, name) {
+            Some(child) => Ok(child),
+            None if name.is_empty() || name == "." || name == ".." => Err(missing),
+            None => Ok(self.push_node(directory, name, NodeKind::File(0))),
+        }
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id].parent
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].kind, NodeKind::Directory(_))
+    }
+
+    pub fn children(&self, id: NodeId) -> Vec<NodeId> {
+        match &self.nodes[id].kind {
+            NodeKind::Directory(children) => children.values().copied().collect(),
+            NodeKind::File(_) => vec![],
+        }
+    }
+
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[id].kind {
+            NodeKind::Directory(children) => children.get(name).copied(),
+            NodeKind::File(_) => None,
+        }
+    }
+
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        self.resolve(ROOT, path)
+    }
+
+    pub fn resolve(&self, from: NodeId, path: &str) -> Option<NodeId> {
+        let start = if path.starts_with('/') { ROOT } else { from };
+        segments(path).try_fold(start, |id, name| match name {
+            ".." => Some(self.parent(id).unwrap_or(ROOT)),
+            name => self.child(id, name),
+        })
+    }
+
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.nodes[current].parent {
+            names.push(self.nodes[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn depth(&self, id: NodeId) -> usize {
+        let mut depth = 0;
+        let mut current = id;
+        while let Some(parent) = self.nodes[current].parent {
+            depth += 1;
+            current = parent;
+        }
+        depth
+    }
+
+    pub fn size(&self, id: NodeId) -> usize {
+        if let Some(size) = self.nodes[id].size.get() {
+            return size;
+        }
+        let size = match &self.nodes[id].kind {
+            NodeKind::File(size) => *size,
+            NodeKind::Directory(children) => children.values().map(|&child| self.size(child)).sum(),
+        };
+        self.nodes[id].size.set(Some(size));
+        size
+    }
+
+    pub fn walk(&self) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        let mut pending = vec![ROOT];
+        while let Some(id) = pending.pop() {
+            order.push(id);
+            pending.extend(self.children(id).into_iter().rev());
+        }
+        order
+    }
+
+    pub fn directories(&self) -> Vec<NodeId> {
+        self.walk()
+            .into_iter()
+            .filter(|&id| self.is_dir(id))
+            .collect()
+    }
+
+    pub fn files(&self) -> Vec<NodeId> {
+        self.walk()
+            .into_iter()
+            .filter(|&id| !self.is_dir(id))
+            .collect()
+    }
+}
+
+pub fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|name| !name.is_empty() && *name != ".")
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Issue {
+    fn at(self, command: usize) -> Self {
+        match self {
+            Self::UnlistedDirectory { path, .. } => Self::UnlistedDirectory { command, path },
+            Self::AboveRoot { .. } => Self::AboveRoot { command },
+            Self::ConflictingSize {
+                path,
+                previous,
+                size,
+                ..
+            } => Self::ConflictingSize {
+                command,
+                path,
+                previous,
+                size,
+            },
+            Self::KindConflict { path, .. } => Self::KindConflict { command, path },
+            Self::MalformedEntry { entry, .. } => Self::MalformedEntry { command, entry },
+            Self::MissingEntry { path, .. } => Self::MissingEntry { command, path },
+            Self::RemovedWorkingDirectory { path, .. } => {
+                Self::RemovedWorkingDirectory { command, path }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnlistedDirectory { command, path } => {
+                write!(
+                    f,
+                    "command {}: cd into unlisted directory {}",
+                    command, path
+                )
+            }
+            Self::AboveRoot { command } => write!(f, "command {}: cd above /", command),
+            Self::ConflictingSize {
+                command,
+                path,
+                previous,
+                size,
+            } => write!(
+                f,
+                "command {}: {} listed with size {} after {}",
+                command, path, size, previous
+            ),
+            Self::KindConflict { command, path } => {
+                write!(
+                    f,
+                    "command {}: {} is both a file and a directory",
+                    command, path
+                )
+            }
+            Self::MalformedEntry { command, entry } => {
+                write!(f, "command {}: malformed ls entry {:?}", command, entry)
+            }
+            Self::MissingEntry { command, path } => {
+                write!(f, "command {}: {} does not exist", command, path)
+            }
+            Self::RemovedWorkingDirectory { command, path } => {
+                write!(f, "command {}: removed working directory {}", command, path)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{directories_size, get_files, load_file, parse_data};
+
+    #[test]
+    fn sizes_match_flat_directory_sizes() {
+        for file_path in ["./resources/test_data.txt", "./resources/puzzle.txt"] {
+            let commands = parse_data(load_file(file_path));
+            let (file_system, issues) = FileSystem::from_commands(&commands);
+            assert_eq!(issues, vec![]);
+            let directories = directories_size(get_files(commands));
+            for directory in file_system.directories() {
+                let path = file_system.path(directory);
+                let key = ["/".to_string()]
+                    .into_iter()
+                    .chain(
+                        path.split('/')
+                            .filter(|name| !name.is_empty())
+                            .map(str::to_string),
+                    )
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    directories.get(&key).copied().unwrap_or(0),
+                    file_system.size(directory)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn traversal_in_alphabetical_pre_order() {
+        let commands = parse_data(load_file("./resources/test_data.txt"));
+        let (file_system, _) = FileSystem::from_commands(&commands);
+        let paths = file_system
+            .walk()
+            .into_iter()
+            .map(|id| file_system.path(id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/", "/a", "/a/e", "/a/e/i", "/a/f", "/a/g", "/a/h.lst", "/b.txt", "/c.dat", "/d",
+                "/d/d.ext", "/d/d.log", "/d/j", "/d/k"
+            ]
+        );
+        let e = file_system.lookup("/a/e").unwrap();
+        assert_eq!(file_system.depth(e), 2);
+        assert_eq!(file_system.size(e), 584);
+        assert_eq!(file_system.parent(e), file_system.lookup("a"));
+        assert_eq!(file_system.size(ROOT), 48381165);
+        assert_eq!(file_system.files().len(), 10);
+    }
+
+    #[test]
+    fn shell_commands_update_the_tree() {
+        let commands = parse_data(load_file("./resources/test_data_shell.txt"));
+        let (file_system, issues) = FileSystem::from_commands(&commands);
+        assert_eq!(
+            issues,
+            vec![Issue::UnlistedDirectory {
+                command: 3,
+                path: "/a/e".to_string()
+            }]
+        );
+        assert_eq!(file_system.size(ROOT), 48381165 - 8504156);
+        assert_eq!(file_system.lookup("/c.dat"), None);
+        assert_eq!(file_system.lookup("/a/tmp"), None);
+        let new_log = file_system.lookup("/a/new.log").unwrap();
+        assert!(!file_system.is_dir(new_log));
+        assert_eq!(file_system.size(new_log), 0);
+        assert_eq!(file_system.files().len(), 10);
+        let d = file_system.lookup("/d").unwrap();
+        assert_eq!(
+            file_system.resolve(d, "../a/./e/i"),
+            file_system.lookup("/a/e/i")
+        );
+    }
+
+    #[test]
+    fn missing_paths_are_reported() {
+        let data = "$ cd /\n$ mkdir a/b\n$ touch a/b/c x/y\n$ rm a/z\n$ cd a/b/c\n$ mkdir /a/b/c/d";
+        let (file_system, issues) = FileSystem::from_commands(&parse_data(data.to_string()));
+        assert_eq!(
+            issues,
+            vec![
+                Issue::MissingEntry {
+                    command: 3,
+                    path: "x/y".to_string()
+                },
+                Issue::MissingEntry {
+                    command: 4,
+                    path: "a/z".to_string()
+                },
+                Issue::KindConflict {
+                    command: 5,
+                    path: "/a/b/c".to_string()
+                },
+                Issue::KindConflict {
+                    command: 6,
+                    path: "/a/b/c".to_string()
+                },
+            ]
+        );
+        assert_eq!(file_system.directories().len(), 3);
+    }
+
+    #[test]
+    fn inconsistencies_are_reported() {
+        let data = "$ cd /\n$ ls\ndir a\n10 b\n$ cd x\n$ cd ..\n$ ls\n20 b\ndir e\n\
+                    $ cd b\n$ cd ..\n$ ls\n5 a\nnonsense";
+        let (file_system, issues) = FileSystem::from_commands(&parse_data(data.to_string()));
+        assert_eq!(
+            issues,
+            vec![
+                Issue::UnlistedDirectory {
+                    command: 3,
+                    path: "/x".to_string()
+                },
+                Issue::ConflictingSize {
+                    command: 5,
+                    path: "/b".to_string(),
+                    previous: 10,
+                    size: 20
+                },
+                Issue::KindConflict {
+                    command: 6,
+                    path: "/b".to_string()
+                },
+                Issue::AboveRoot { command: 7 },
+                Issue::KindConflict {
+                    command: 8,
+                    path: "/a".to_string()
+                },
+                Issue::MalformedEntry {
+                    command: 8,
+                    entry: "nonsense".to_string()
+                },
+            ]
+        );
+        assert!(file_system.lookup("/e").is_some());
+        assert_eq!(file_system.size(ROOT), 20);
+    }
+
+    #[test]
+    fn removing_the_working_directory_moves_to_its_parent() {
+        let data = "$ cd /\n$ mkdir a/b\n$ cd /a/b\n$ rm /a\n$ ls\n100 x\n$ rm /\n";
+        let (file_system, issues) = FileSystem::from_commands(&parse_data(data.to_string()));
+        assert_eq!(
+            issues,
+            vec![Issue::RemovedWorkingDirectory {
+                command: 4,
+                path: "/a/b".to_string()
+            }]
+        );
+        assert_eq!(file_system.walk(), vec![ROOT]);
+        assert_eq!(file_system.node(1).parent, None);
+        assert_eq!(file_system.node(3).parent, None);
+
+        let data = "$ cd /\n$ mkdir a\n$ cd a\n$ rm /a\n$ ls\n100 x\n";
+        let (file_system, _) = FileSystem::from_commands(&parse_data(data.to_string()));
+        assert_eq!(file_system.size(file_system.lookup("/x").unwrap()), 100);
+    }
+
+    #[test]
+    fn names_may_contain_spaces() {
+        let data = "$ cd /\n$ ls\ndir my docs\n12 a b.txt\n$ cd my docs\n$ ls\n3 notes  v2\n";
+        let commands = parse_data(data.to_string());
+        let (file_system, issues) = FileSystem::from_commands(&commands);
+        assert_eq!(issues, vec![]);
+        assert_eq!(
+            file_system.size(file_system.lookup("/a b.txt").unwrap()),
+            12
+        );
+        assert_eq!(
+            file_system.size(file_system.lookup("/my docs/notes  v2").unwrap()),
+            3
+        );
+        let directories = directories_size(get_files(commands));
+        assert_eq!(directories[&vec!["/".to_string()]], 15);
+        assert_eq!(
+            directories[&vec!["/".to_string(), "my docs".to_string()]],
+            3
+        );
+    }
+}
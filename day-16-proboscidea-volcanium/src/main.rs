@@ -1,8 +1,11 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    fs,
+    env, fs,
+    time::Duration,
 };
 
+use limits::{Guard, Limits, Runner, SolveError};
+
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
@@ -54,23 +57,27 @@ fn solve(
     current_valve: &String,
     available_minutes: i32,
     valves_to_open: HashMap<&String, &(u32, Vec<String>)>,
-) -> i32 {
+    guard: &mut Guard,
+) -> Result<i32, SolveError> {
     let mut queue = VecDeque::from([(current_valve, valves_to_open, available_minutes, 0)]);
 
     let mut max_flow = 0;
 
-    let mut con = HashMap::new();
+    let mut con: HashMap<_, (i32, i32)> = HashMap::new();
 
     while let Some((valve, valves_to_open, available_minutes, current_flow)) = queue.pop_front() {
-        let mut vto: Vec<_> = valves_to_open.iter().map(|(n, _)| n.clone()).collect();
+        guard.step()?;
+        guard.hold(queue.len() + con.len())?;
+        let mut vto: Vec<_> = valves_to_open.keys().copied().collect();
         vto.sort();
-        if let Some(r) = con.get(&vto) {
-            if r > &current_flow {
+        let key = (valve, vto);
+        if let Some(r) = con.get(&key) {
+            if r.0 >= current_flow && r.1 >= available_minutes {
                 continue;
             }
         }
 
-        con.insert(vto, current_flow);
+        con.insert(key, (current_flow, available_minutes));
 
         for (next_valve, (flow, _)) in &valves_to_open {
             if let Some(distance) = find_shortest_path(data, valve, next_valve) {
@@ -95,7 +102,7 @@ fn solve(
         }
     }
 
-    max_flow
+    Ok(max_flow)
 }
 
 fn solve_2(
@@ -103,25 +110,27 @@ fn solve_2(
     current_valve: &String,
     available_minutes: i32,
     valves_to_open: HashMap<&String, &(u32, Vec<String>)>,
-    con: &mut HashMap<Vec<String>, (i32, i32)>,
-) -> i32 {
+    guard: &mut Guard,
+) -> Result<i32, SolveError> {
     let mut queue = VecDeque::from([(current_valve, valves_to_open, available_minutes, 0)]);
 
     let mut max_flow = 0;
 
+    let mut con: HashMap<_, (i32, i32)> = HashMap::new();
+
     while let Some((valve, valves_to_open, available_minutes, current_flow)) = queue.pop_front() {
-        let mut vto: Vec<String> = valves_to_open
-            .iter()
-            .map(|(n, _)| n.to_owned().clone())
-            .collect();
+        guard.step()?;
+        guard.hold(queue.len() + con.len())?;
+        let mut vto: Vec<_> = valves_to_open.keys().copied().collect();
         vto.sort();
-        if let Some(r) = con.get(&vto) {
-            if r.0 > current_flow && r.1 > available_minutes {
+        let key = (valve, vto);
+        if let Some(r) = con.get(&key) {
+            if r.0 >= current_flow && r.1 >= available_minutes {
                 continue;
             }
         }
 
-        con.insert(vto, (current_flow, available_minutes));
+        con.insert(key, (current_flow, available_minutes));
 
         for (next_valve, (flow, _)) in &valves_to_open {
             if let Some(distance) = find_shortest_path(data, valve, next_valve) {
@@ -146,54 +155,61 @@ fn solve_2(
         }
     }
 
-    max_flow
+    Ok(max_flow)
 }
 
-fn solve_part_1(file_path: &str) -> u32 {
+fn solve_part_1(file_path: &str, limits: &Limits) -> Result<u32, SolveError> {
     let data = load_file(file_path);
     let data = parse_data(data);
     let available_minutes = 30;
     let start_valve = "AA".to_string();
     let valves_to_open: HashMap<_, _> = data.iter().filter(|(_, (flow, _))| flow > &0).collect();
+    let mut guard = Guard::new(limits);
     solve(
         &data,
         &start_valve.to_string(),
         available_minutes,
         valves_to_open,
-    ) as u32
+        &mut guard,
+    )
+    .map(|max_flow| max_flow as u32)
 }
 
 fn partition_sub<Type>(
-    arr: &Vec<Type>,
+    arr: &[Type],
     i: usize,
-    n: usize,
     k: usize,
-    nos: usize,
     v: &mut Vec<VecDeque<Type>>,
     result: &mut Vec<Vec<VecDeque<Type>>>,
-) where
+    guard: &mut Guard,
+) -> Result<(), SolveError>
+where
     Type: Clone,
 {
+    guard.step()?;
     if i >= arr.len() {
+        guard.hold(result.len() + 1)?;
         result.push(v.clone());
-        return;
+        return Ok(());
     }
 
     for j in 0..k {
-        if v[j].len() > 0 {
-            v[j].push_back(arr[i].clone());
-            partition_sub(arr, i + 1, n, k, nos, v, result);
-            v[j].pop_back();
-        } else {
-            v[j].push_back(arr[i].clone());
-            partition_sub(arr, i + 1, n, k, nos + 1, v, result);
-            v[j].pop_back();
+        let was_empty = v[j].is_empty();
+        v[j].push_back(arr[i].clone());
+        partition_sub(arr, i + 1, k, v, result, guard)?;
+        v[j].pop_back();
+        if was_empty {
             break;
         }
     }
+    Ok(())
 }
 
-fn part_k_subsets<Type>(arr: &Vec<Type>, k: usize) -> Vec<Vec<VecDeque<Type>>>
+fn part_k_subsets<Type>(
+    arr: &[Type],
+    k: usize,
+    guard: &mut Guard,
+) -> Result<Vec<Vec<VecDeque<Type>>>, SolveError>
 where
     Type: Clone,
 {
@@ -201,71 +217,124 @@ where
     let mut v = vec![VecDeque::new(); k];
     let mut result = Vec::new();
     if k != 0 && k <= n {
-        partition_sub(arr, 0, n, k, 0, &mut v, &mut result);
+        partition_sub(arr, 0, k, &mut v, &mut result, guard)?;
     }
-    result
+    Ok(result)
 }
 
-fn solve_part_2(file_path: &str) -> u32 {
+fn solve_part_2(file_path: &str, limits: &Limits) -> Result<u32, SolveError> {
     let data = load_file(file_path);
     let data = parse_data(data);
     let available_minutes = 26;
     let start_valve = "AA".to_string();
-    let valves_to_open: HashMap<_, _> = data.iter().filter(|(_, (flow, _))| flow > &0).collect();
+    let valves_to_open: Vec<_> = data.iter().filter(|(_, (flow, _))| flow > &0).collect();
 
-    let subsets = part_k_subsets(&valves_to_open.into_iter().collect(), 2);
+    let mut guard = Guard::new(limits);
+    let subsets = part_k_subsets(&valves_to_open, 2, &mut guard)?;
     let mut max_flow = 0;
-    let mut con = HashMap::new();
     for sets in subsets.iter().filter(|s| s[0].len() * 2 < 20) {
         let result1 = solve_2(
             &data,
             &start_valve.to_string(),
             available_minutes,
             sets[0].iter().cloned().collect(),
-            &mut con,
-        ) as u32;
+            &mut guard,
+        )? as u32;
         let result2 = solve_2(
             &data,
             &start_valve.to_string(),
             available_minutes,
             sets[1].iter().cloned().collect(),
-            &mut con,
-        ) as u32;
+            &mut guard,
+        )? as u32;
         max_flow = max_flow.max(result1 + result2);
     }
 
-    max_flow
+    Ok(max_flow)
 }
 
-fn part_1(file_path: &str) {
-    let result = solve_part_1(file_path);
+fn part_1(file_path: &str, runner: &Runner) {
+    let result = runner.run(|limits| solve_part_1(file_path, limits));
     println!("Part 1 result: {:?}", result);
 }
 
-fn part_2(file_path: &str) {
-    let result = solve_part_2(file_path);
+fn part_2(file_path: &str, runner: &Runner) {
+    let result = runner.run(|limits| solve_part_2(file_path, limits));
     println!("Part 2 result: {:?}    2469", result);
 }
 
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    const TIME_LIMIT: Duration = Duration::from_secs(60);
+    let runner = match Runner::new(TIME_LIMIT).with_args(env::args().skip(1)) {
+        Ok(runner) => runner,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    part_1(FILE_PATH, &runner);
+    part_2(FILE_PATH, &runner);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use limits::Budget;
+    use std::{
+        sync::{atomic::AtomicBool, Arc},
+        time::Instant,
+    };
 
     #[test]
     fn test_part_1() {
-        let result = solve_part_1("./resources/test_data.txt");
-        assert_eq!(result, 1651);
+        let result = solve_part_1("./resources/test_data.txt", &Limits::default());
+        assert_eq!(result, Ok(1651));
     }
 
     #[test]
     fn test_part_2() {
-        let result = solve_part_2("./resources/test_data.txt");
-        assert_eq!(result, 1707);
+        let result = solve_part_2("./resources/test_data.txt", &Limits::default());
+        assert_eq!(result, Ok(1707));
+    }
+
+    #[test]
+    fn test_step_budget_exceeded() {
+        let limits = Limits {
+            max_steps: Some(10),
+            ..Default::default()
+        };
+        let result = solve_part_2("./resources/test_data.txt", &limits);
+        assert_eq!(result, Err(SolveError::BudgetExceeded(Budget::Steps)));
+    }
+
+    #[test]
+    fn test_memory_budget_exceeded() {
+        let limits = Limits {
+            max_states: Some(3),
+            ..Default::default()
+        };
+        let result = solve_part_1("./resources/test_data.txt", &limits);
+        assert_eq!(result, Err(SolveError::BudgetExceeded(Budget::Memory)));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let limits = Limits {
+            cancellation: Some(Arc::new(AtomicBool::new(true))),
+            ..Default::default()
+        };
+        let result = solve_part_1("./resources/test_data.txt", &limits);
+        assert_eq!(result, Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limits = Limits {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let result = solve_part_2("./resources/test_data.txt", &limits);
+        assert_eq!(result, Err(SolveError::Timeout));
     }
 }
@@ -1,8 +1,11 @@
 use std::{
-    fs,
+    env, fs,
     ops::{Add, Sub},
+    time::Duration,
 };
 
+use limits::{Guard, Limits, Runner, SolveError};
+
 fn load_file(file_path: &str) -> String {
     fs::read_to_string(file_path).expect("Should have been able to read the file")
 }
@@ -170,14 +173,15 @@ impl Status {
 struct Solver;
 
 impl Solver {
-    pub fn solve(minutes_left: u8, status: Status) -> usize {
+    pub fn solve(minutes_left: u8, status: Status, guard: &mut Guard) -> Result<usize, SolveError> {
+        guard.step()?;
         let Status {
             blueprint,
-            mut robots,
+            robots,
             materials,
         } = status;
         if minutes_left == 0 {
-            return materials.geodes;
+            return Ok(materials.geodes);
         }
         let collected_materials = robots.collect_materials();
         let mut max_geode = 0;
@@ -197,7 +201,7 @@ impl Solver {
             .unwrap();
 
         if Self::can_build_geode_robot(&blueprint, &materials) {
-            let mut robots = robots.clone();
+            let mut robots = robots;
             robots.geode_robots += 1;
             max_geode = max_geode.max(Self::solve(
                 minutes_left - 1,
@@ -206,12 +210,13 @@ impl Solver {
                     robots,
                     materials: materials + collected_materials - blueprint.geode_robot_cost,
                 },
-            ));
-            return max_geode;
+                guard,
+            )?);
+            return Ok(max_geode);
         } else if Self::can_build_obsidian_robot(&blueprint, &materials)
             && need_more_obsidian_robots
         {
-            let mut robots = robots.clone();
+            let mut robots = robots;
             robots.obsidian_robots += 1;
             max_geode = max_geode.max(Self::solve(
                 minutes_left - 1,
@@ -220,10 +225,11 @@ impl Solver {
                     robots,
                     materials: materials + collected_materials - blueprint.obsidian_robot_cost,
                 },
-            ));
+                guard,
+            )?);
         } else {
             if Self::can_build_clay_robot(&blueprint, &materials) && need_more_clay_robots {
-                let mut robots = robots.clone();
+                let mut robots = robots;
                 robots.clay_robots += 1;
                 max_geode = max_geode.max(Self::solve(
                     minutes_left - 1,
@@ -232,10 +238,11 @@ impl Solver {
                         robots,
                         materials: materials + collected_materials - blueprint.clay_robot_cost,
                     },
-                ));
+                    guard,
+                )?);
             }
             if Self::can_build_ore_robot(&blueprint, &materials) && need_more_ore_robots {
-                let mut robots = robots.clone();
+                let mut robots = robots;
                 robots.ore_robots += 1;
                 max_geode = max_geode.max(Self::solve(
                     minutes_left - 1,
@@ -244,7 +251,8 @@ impl Solver {
                         robots,
                         materials: materials + collected_materials - blueprint.ore_robot_cost,
                     },
-                ));
+                    guard,
+                )?);
             }
             {
                 max_geode = max_geode.max(Self::solve(
@@ -254,11 +262,12 @@ impl Solver {
                         robots,
                         materials: materials + collected_materials,
                     },
-                ));
+                    guard,
+                )?);
             }
         };
 
-        max_geode
+        Ok(max_geode)
     }
 
     fn can_build_geode_robot(blueprint: &Blueprint, materials: &Materials) -> bool {
@@ -284,63 +293,120 @@ impl Solver {
     }
 }
 
-fn solve_part_1(file_path: &str) -> usize {
+fn solve_part_1(file_path: &str, limits: &Limits) -> Result<usize, SolveError> {
     let data = load_file(file_path);
     let blueprints = parse_data(data);
-    let statuses = blueprints
-        .into_iter()
-        .map(|blueprint| Status::new(blueprint));
+    let statuses = blueprints.into_iter().map(Status::new);
+    let mut guard = Guard::new(limits);
     statuses
         .into_iter()
-        .map(|status| status.blueprint.id * Solver::solve(24, status))
+        .map(|status| Ok(status.blueprint.id * Solver::solve(24, status, &mut guard)?))
         .sum()
 }
 
-fn solve_part_2(file_path: &str) -> usize {
+fn solve_part_2(file_path: &str, limits: &Limits) -> Result<usize, SolveError> {
     let data = load_file(file_path);
     let blueprints = parse_data(data);
-    let statuses = blueprints
-        .into_iter()
-        .map(|blueprint| Status::new(blueprint));
+    let statuses = blueprints.into_iter().map(Status::new);
+    let mut guard = Guard::new(limits);
     statuses
         .into_iter()
         .take(3)
-        .map(|status| Solver::solve(32, status))
+        .map(|status| Solver::solve(32, status, &mut guard))
         .product()
 }
 
-fn part_1(file_path: &str) {
-    let result = solve_part_1(file_path);
+fn part_1(file_path: &str, runner: &Runner) {
+    let result = runner.run(|limits| solve_part_1(file_path, limits));
     println!("Part 1 result: {:?}", result);
 }
 
-fn part_2(file_path: &str) {
-    let result = solve_part_2(file_path);
+fn part_2(file_path: &str, runner: &Runner) {
+    let result = runner.run(|limits| solve_part_2(file_path, limits));
     println!("Part 2 result: {:?}", result);
 }
 
 fn main() {
     const FILE_PATH: &str = "./resources/puzzle.txt";
-    part_1(FILE_PATH);
-    part_2(FILE_PATH);
+    const TIME_LIMIT: Duration = Duration::from_secs(120);
+    let runner = match Runner::new(TIME_LIMIT).with_args(env::args().skip(1)) {
+        Ok(runner) => runner,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    part_1(FILE_PATH, &runner);
+    part_2(FILE_PATH, &runner);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use limits::Budget;
+    use std::{
+        sync::{atomic::AtomicBool, Arc},
+        time::Instant,
+    };
 
     #[test]
     fn test_part_1() {
-        let result = solve_part_1("./resources/test_data.txt");
-        assert_eq!(result, 33);
+        let result = solve_part_1("./resources/test_data.txt", &Limits::default());
+        assert_eq!(result, Ok(33));
     }
 
     #[test]
     fn test_solver() {
         let data = load_file("./resources/test_data.txt");
         let blueprints = parse_data(data);
-        let result = Solver::solve(24, Status::new(blueprints[0]));
-        assert_eq!(result, 9);
+        let limits = Limits::default();
+        let result = Solver::solve(24, Status::new(blueprints[0]), &mut Guard::new(&limits));
+        assert_eq!(result, Ok(9));
+    }
+
+    #[test]
+    fn test_solver_limits() {
+        let data = load_file("./resources/test_data.txt");
+        let blueprints = parse_data(data);
+        let status = Status::new(blueprints[0]);
+
+        let limits = Limits {
+            max_steps: Some(1000),
+            ..Default::default()
+        };
+        let result = Solver::solve(24, status, &mut Guard::new(&limits));
+        assert_eq!(result, Err(SolveError::BudgetExceeded(Budget::Steps)));
+
+        let limits = Limits {
+            cancellation: Some(Arc::new(AtomicBool::new(true))),
+            ..Default::default()
+        };
+        let result = Solver::solve(24, status, &mut Guard::new(&limits));
+        assert_eq!(result, Err(SolveError::Cancelled));
+
+        let limits = Limits {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let result = Solver::solve(24, status, &mut Guard::new(&limits));
+        assert_eq!(result, Err(SolveError::Timeout));
+    }
+
+    #[test]
+    fn test_step_budget_covers_whole_run() {
+        let data = load_file("./resources/test_data.txt");
+        let blueprints = parse_data(data);
+        let unlimited = Limits::default();
+        let mut guard = Guard::new(&unlimited);
+        Solver::solve(24, Status::new(blueprints[0]), &mut guard).unwrap();
+        let first_blueprint_steps = guard.steps();
+
+        let limits = Limits {
+            max_steps: Some(first_blueprint_steps + 1),
+            ..Default::default()
+        };
+        let result = solve_part_1("./resources/test_data.txt", &limits);
+        assert_eq!(result, Err(SolveError::BudgetExceeded(Budget::Steps)));
     }
 
     #[test]
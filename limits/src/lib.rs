@@ -0,0 +1,248 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    Steps,
+    Memory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    Timeout,
+    Cancelled,
+    BudgetExceeded(Budget),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Limits {
+    pub deadline: Option<Instant>,
+    pub cancellation: Option<Arc<AtomicBool>>,
+    pub max_steps: Option<usize>,
+    pub max_states: Option<usize>,
+}
+
+pub struct Guard<'a> {
+    limits: &'a Limits,
+    steps: usize,
+}
+
+impl<'a> Guard<'a> {
+    pub fn new(limits: &'a Limits) -> Self {
+        Self { limits, steps: 0 }
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn step(&mut self) -> Result<(), SolveError> {
+        self.steps += 1;
+        if self.limits.max_steps.is_some_and(|max| self.steps > max) {
+            return Err(SolveError::BudgetExceeded(Budget::Steps));
+        }
+        if let Some(cancellation) = &self.limits.cancellation {
+            if cancellation.load(Ordering::Relaxed) {
+                return Err(SolveError::Cancelled);
+            }
+        }
+        if self
+            .limits
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(SolveError::Timeout);
+        }
+        Ok(())
+    }
+
+    pub fn hold(&self, states: usize) -> Result<(), SolveError> {
+        if self.limits.max_states.is_some_and(|max| states > max) {
+            return Err(SolveError::BudgetExceeded(Budget::Memory));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidValue { option: String, value: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct Runner {
+    timeout: Option<Duration>,
+    max_steps: Option<usize>,
+    max_states: Option<usize>,
+    cancellation: Arc<AtomicBool>,
+}
+
+impl Runner {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            max_steps: None,
+            max_states: None,
+            cancellation: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_args(mut self, args: impl IntoIterator<Item = String>) -> Result<Self, ArgError> {
+        let mut args = args.into_iter();
+        while let Some(option) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| ArgError::MissingValue(option.clone()))?;
+            let number = value.parse::<usize>().map_err(|_| ArgError::InvalidValue {
+                option: option.clone(),
+                value: value.clone(),
+            })?;
+            let limit = (number > 0).then_some(number);
+            match option.as_str() {
+                "--timeout" => {
+                    self.timeout = limit.map(|seconds| Duration::from_secs(seconds as u64))
+                }
+                "--max-steps" => self.max_steps = limit,
+                "--max-states" => self.max_states = limit,
+                _ => return Err(ArgError::UnknownOption(option)),
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn cancellation(&self) -> Arc<AtomicBool> {
+        self.cancellation.clone()
+    }
+
+    pub fn limits(&self) -> Limits {
+        Limits {
+            deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+            cancellation: Some(self.cancellation.clone()),
+            max_steps: self.max_steps,
+            max_states: self.max_states,
+        }
+    }
+
+    pub fn run<T>(
+        &self,
+        solve: impl FnOnce(&Limits) -> Result<T, SolveError>,
+    ) -> Result<T, SolveError> {
+        solve(&self.limits())
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "time limit exceeded"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::BudgetExceeded(Budget::Steps) => write!(f, "step budget exceeded"),
+            Self::BudgetExceeded(Budget::Memory) => write!(f, "memory budget exceeded"),
+        }
+    }
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOption(option) => write!(
+                f,
+                "unknown option {}, expected --timeout, --max-steps or --max-states",
+                option
+            ),
+            Self::MissingValue(option) => write!(f, "{} needs a value", option),
+            Self::InvalidValue { option, value } => {
+                write!(f, "{} expects a number, got {:?}", option, value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn guard_enforces_every_limit() {
+        let limits = Limits {
+            max_steps: Some(2),
+            max_states: Some(5),
+            ..Default::default()
+        };
+        let mut guard = Guard::new(&limits);
+        assert_eq!(guard.step(), Ok(()));
+        assert_eq!(guard.step(), Ok(()));
+        assert_eq!(guard.step(), Err(SolveError::BudgetExceeded(Budget::Steps)));
+        assert_eq!(guard.steps(), 3);
+        assert_eq!(guard.hold(5), Ok(()));
+        assert_eq!(
+            guard.hold(6),
+            Err(SolveError::BudgetExceeded(Budget::Memory))
+        );
+
+        let limits = Limits {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        assert_eq!(Guard::new(&limits).step(), Err(SolveError::Timeout));
+    }
+
+    #[test]
+    fn runner_applies_overrides_to_every_run() {
+        let runner = Runner::new(Duration::from_secs(60))
+            .with_args(args(&["--max-steps", "3", "--timeout", "0"]))
+            .unwrap();
+        let limits = runner.limits();
+        assert_eq!(limits.deadline, None);
+        assert_eq!(limits.max_steps, Some(3));
+        assert_eq!(limits.max_states, None);
+
+        let count = |limits: &Limits| {
+            let mut guard = Guard::new(limits);
+            while guard.step().is_ok() {}
+            Ok(guard.steps())
+        };
+        assert_eq!(runner.run(count), Ok(4));
+        assert_eq!(runner.run(count), Ok(4));
+
+        runner.cancellation().store(true, Ordering::Relaxed);
+        assert_eq!(
+            runner.run(|limits| Guard::new(limits).step()),
+            Err(SolveError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let runner = || Runner::new(Duration::from_secs(1));
+        assert_eq!(
+            runner().with_args(args(&["--max-steps"])).unwrap_err(),
+            ArgError::MissingValue("--max-steps".to_string())
+        );
+        assert_eq!(
+            runner()
+                .with_args(args(&["--timeout", "soon"]))
+                .unwrap_err(),
+            ArgError::InvalidValue {
+                option: "--timeout".to_string(),
+                value: "soon".to_string()
+            }
+        );
+        assert_eq!(
+            runner().with_args(args(&["--fast", "1"])).unwrap_err(),
+            ArgError::UnknownOption("--fast".to_string())
+        );
+    }
+}